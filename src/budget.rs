@@ -0,0 +1,145 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Global allocator that caps live heap memory, which is how the CLI enforces
+/// `--memory-limit`. Install it with `#[global_allocator]` in a binary; until
+/// [`Budget::set_limit`] is called it only counts.
+///
+/// An allocation that would exceed the limit fails, and Rust aborts the
+/// process after a message naming the limit. Fallible allocations such as
+/// `Vec::try_reserve` get an error instead.
+pub struct Budget {
+    limit: AtomicUsize,
+    in_use: AtomicUsize,
+    reported: AtomicBool,
+}
+
+impl Budget {
+    pub const fn new() -> Self {
+        Budget {
+            limit: AtomicUsize::new(usize::MAX),
+            in_use: AtomicUsize::new(0),
+            reported: AtomicBool::new(false),
+        }
+    }
+
+    /// Caps live heap bytes from now on, counting what is already allocated.
+    pub fn set_limit(&self, bytes: u64) {
+        let bytes = usize::try_from(bytes).unwrap_or(usize::MAX);
+        self.limit.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Heap bytes currently allocated through this allocator.
+    pub fn in_use(&self) -> usize {
+        self.in_use.load(Ordering::Relaxed)
+    }
+
+    fn reserve(&self, size: usize) -> bool {
+        let limit = self.limit.load(Ordering::Relaxed);
+        let before = self.in_use.fetch_add(size, Ordering::Relaxed);
+        if before.saturating_add(size) <= limit {
+            return true;
+        }
+        self.in_use.fetch_sub(size, Ordering::Relaxed);
+        // Formatting integers into stderr does not allocate, so this cannot
+        // recurse into the allocator.
+        if !self.reported.swap(true, Ordering::Relaxed) {
+            let _ = writeln!(
+                std::io::stderr(),
+                "Error: memory limit of {} bytes exceeded ({} in use, {} requested); raise --memory-limit",
+                limit,
+                before,
+                size
+            );
+        }
+        false
+    }
+
+    fn release(&self, size: usize) {
+        self.in_use.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::new()
+    }
+}
+
+// SAFETY: every call is forwarded to `System`; the counters only decide
+// whether to forward an allocation and never touch the memory itself.
+unsafe impl GlobalAlloc for Budget {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if !self.reserve(layout.size()) {
+            return std::ptr::null_mut();
+        }
+        let ptr = unsafe { System.alloc(layout) };
+        if ptr.is_null() {
+            self.release(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if !self.reserve(layout.size()) {
+            return std::ptr::null_mut();
+        }
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if ptr.is_null() {
+            self.release(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        self.release(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let old_size = layout.size();
+        if new_size > old_size && !self.reserve(new_size - old_size) {
+            return std::ptr::null_mut();
+        }
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if new_ptr.is_null() {
+            if new_size > old_size {
+                self.release(new_size - old_size);
+            }
+        } else if new_size < old_size {
+            self.release(old_size - new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allocations_past_the_limit_fail() {
+        let budget = Budget::new();
+        budget.set_limit(1024);
+        let small = Layout::from_size_align(512, 8).unwrap();
+        let large = Layout::from_size_align(2048, 8).unwrap();
+        unsafe {
+            let a = budget.alloc(small);
+            assert!(!a.is_null());
+            assert_eq!(budget.in_use(), 512);
+            assert!(budget.alloc(large).is_null());
+            assert_eq!(budget.in_use(), 512);
+
+            let a = budget.realloc(a, small, 1024);
+            assert!(!a.is_null());
+            assert!(
+                budget
+                    .realloc(a, Layout::from_size_align(1024, 8).unwrap(), 1025)
+                    .is_null()
+            );
+            budget.dealloc(a, Layout::from_size_align(1024, 8).unwrap());
+        }
+        assert_eq!(budget.in_use(), 0);
+    }
+}
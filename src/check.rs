@@ -0,0 +1,610 @@
+use std::fmt;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{Contract, ContractReport};
+use crate::identifiers::{self, Identifiers};
+use crate::outliers::{self, OutlierMethod, Outliers};
+use crate::report::{
+    ConstantColumn, ConstantColumns, Duplicates, Finding, MissingValues, Severity, percentage,
+};
+use crate::streaming::collect_streaming;
+use crate::target::{TargetOptions, TargetReport, analyze_target};
+
+/// A dataset check: measures one aspect of a frame and turns what it found
+/// into findings.
+///
+/// Each implementation also exposes `measure` and `findings` separately, so
+/// callers that want the measurements (as the validation report does) do not
+/// have to run the check twice.
+pub trait Check {
+    /// Identifier used as [`Finding::check`].
+    fn name(&self) -> &'static str;
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>>;
+}
+
+/// The checks `validate` can run, for turning individual ones off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckKind {
+    Missing,
+    Duplicates,
+    Outliers,
+    /// Columns with a single value, or one value in almost every row
+    Constant,
+    /// Integer and text columns with nearly one distinct value per row, e.g. ids
+    Identifiers,
+    /// Class balance or distribution of the `--target` column
+    Target,
+    /// Features that give the target away; needs `target`
+    Leakage,
+    /// Conformance to the `--schema` contract
+    Contract,
+}
+
+impl CheckKind {
+    pub const ALL: [CheckKind; 8] = [
+        CheckKind::Missing,
+        CheckKind::Duplicates,
+        CheckKind::Outliers,
+        CheckKind::Constant,
+        CheckKind::Identifiers,
+        CheckKind::Target,
+        CheckKind::Leakage,
+        CheckKind::Contract,
+    ];
+}
+
+impl fmt::Display for CheckKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckKind::Missing => write!(f, "missing"),
+            CheckKind::Duplicates => write!(f, "duplicates"),
+            CheckKind::Outliers => write!(f, "outliers"),
+            CheckKind::Constant => write!(f, "constant"),
+            CheckKind::Identifiers => write!(f, "identifiers"),
+            CheckKind::Target => write!(f, "target"),
+            CheckKind::Leakage => write!(f, "leakage"),
+            CheckKind::Contract => write!(f, "contract"),
+        }
+    }
+}
+
+/// Findings for the warnings raised while loading a file, e.g. NDJSON fields
+/// whose type changes between lines.
+pub fn load_warning_findings(warnings: &[String]) -> Vec<Finding> {
+    warnings
+        .iter()
+        .map(|warning| Finding {
+            check: "schema".to_string(),
+            column: None,
+            severity: Severity::Warn,
+            message: warning.clone(),
+        })
+        .collect()
+}
+
+fn threshold_severity(pct: f64, max_pct: Option<f64>) -> Severity {
+    match max_pct {
+        Some(max) if pct > max => Severity::Error,
+        Some(_) => Severity::Info,
+        None => Severity::Warn,
+    }
+}
+
+/// Null values per column.
+#[derive(Debug, Clone, Default)]
+pub struct MissingValuesCheck {
+    /// Percentage above which a column is an error rather than a warning.
+    pub max_pct: Option<f64>,
+}
+
+impl MissingValuesCheck {
+    pub fn measure(&self, df: &DataFrame) -> Vec<MissingValues> {
+        df.get_columns()
+            .iter()
+            .map(|col| MissingValues {
+                column: col.name().to_string(),
+                count: col.null_count(),
+                percentage: percentage(col.null_count(), df.height()),
+                ci: None,
+            })
+            .collect()
+    }
+
+    pub fn findings(&self, missing: &[MissingValues]) -> Vec<Finding> {
+        missing
+            .iter()
+            .filter(|m| m.count > 0)
+            .map(|m| Finding {
+                check: "missing".to_string(),
+                column: Some(m.column.clone()),
+                severity: threshold_severity(m.percentage, self.max_pct),
+                message: match &m.ci {
+                    Some(ci) => format!(
+                        "{} missing values in sample ({:.1}%, {})",
+                        m.count, m.percentage, ci
+                    ),
+                    None => format!("{} missing values ({:.1}%)", m.count, m.percentage),
+                },
+            })
+            .collect()
+    }
+}
+
+impl Check for MissingValuesCheck {
+    fn name(&self) -> &'static str {
+        "missing"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(self.findings(&self.measure(df)))
+    }
+}
+
+/// Rows that exactly repeat an earlier row.
+#[derive(Debug, Clone, Default)]
+pub struct DuplicatesCheck {
+    /// Percentage above which duplicates are an error rather than a warning.
+    pub max_pct: Option<f64>,
+}
+
+impl DuplicatesCheck {
+    pub fn measure(&self, df: &DataFrame) -> Result<Duplicates> {
+        let deduped = df
+            .clone()
+            .lazy()
+            .unique(None, UniqueKeepStrategy::First)
+            .collect()?;
+        let count = df.height() - deduped.height();
+        Ok(Duplicates {
+            count,
+            percentage: percentage(count, df.height()),
+            sampled: false,
+        })
+    }
+
+    pub fn findings(&self, duplicates: &Duplicates) -> Vec<Finding> {
+        if duplicates.count == 0 {
+            return Vec::new();
+        }
+
+        vec![Finding {
+            check: "duplicates".to_string(),
+            column: None,
+            severity: threshold_severity(duplicates.percentage, self.max_pct),
+            message: duplicates.to_string(),
+        }]
+    }
+}
+
+impl Check for DuplicatesCheck {
+    fn name(&self) -> &'static str {
+        "duplicates"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(self.findings(&self.measure(df)?))
+    }
+}
+
+/// Outliers in every numeric column.
+#[derive(Debug, Clone)]
+pub struct OutlierCheck {
+    pub method: OutlierMethod,
+    /// Cut-off for `method`; `None` uses [`OutlierMethod::default_threshold`].
+    pub threshold: Option<f64>,
+    /// Percentage above which a column is an error rather than a warning.
+    pub max_pct: Option<f64>,
+}
+
+impl Default for OutlierCheck {
+    fn default() -> Self {
+        OutlierCheck {
+            method: OutlierMethod::Iqr,
+            threshold: None,
+            max_pct: None,
+        }
+    }
+}
+
+impl OutlierCheck {
+    pub fn measure(&self, df: &DataFrame) -> Result<Outliers> {
+        let threshold = self
+            .threshold
+            .unwrap_or_else(|| self.method.default_threshold());
+        outliers::detect(df, self.method, threshold)
+    }
+
+    pub fn findings(&self, outliers: &Outliers) -> Vec<Finding> {
+        outliers
+            .columns
+            .iter()
+            .filter(|c| c.count > 0)
+            .map(|c| Finding {
+                check: "outliers".to_string(),
+                column: Some(c.column.clone()),
+                severity: threshold_severity(c.percentage, self.max_pct),
+                message: format!(
+                    "{} outliers ({:.1}%) outside [{:.3}, {:.3}]",
+                    c.count, c.percentage, c.lower, c.upper
+                ),
+            })
+            .collect()
+    }
+}
+
+impl Check for OutlierCheck {
+    fn name(&self) -> &'static str {
+        "outliers"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(self.findings(&self.measure(df)?))
+    }
+}
+
+/// Columns that hold one value, or one value in almost every row. They carry
+/// no signal, and often point at a broken upstream join.
+/// Prefix of the per-column value aliases in the constant-column query.
+const VALUE_ALIAS: &str = "__mlcheck_value_";
+const COUNT_ALIAS: &str = "__mlcheck_count";
+const UNIQUE_ALIAS: &str = "__mlcheck_unique";
+
+#[derive(Debug, Clone)]
+pub struct ConstantColumnsCheck {
+    /// Share of rows, in percent, above which the most frequent value makes a
+    /// column near-constant.
+    pub max_dominant_pct: f64,
+}
+
+impl Default for ConstantColumnsCheck {
+    fn default() -> Self {
+        ConstantColumnsCheck {
+            max_dominant_pct: 99.5,
+        }
+    }
+}
+
+impl ConstantColumnsCheck {
+    /// Nulls count as a value of their own, so a column that is half null and
+    /// half one value is not constant. Nested columns are skipped.
+    pub fn measure(&self, df: &DataFrame) -> Result<ConstantColumns> {
+        self.measure_with(&df.clone().lazy(), df.schema(), df.height(), |lf| {
+            lf.collect()
+        })
+    }
+
+    /// Streaming counterpart of [`Self::measure`]. All columns are counted in
+    /// one pass over the input, which holds a table of every distinct value
+    /// of every column at once: ID-like and free-text columns cost memory in
+    /// proportion to the rows.
+    pub fn measure_lazy(
+        &self,
+        lf: &LazyFrame,
+        schema: &Schema,
+        rows: usize,
+    ) -> Result<ConstantColumns> {
+        self.measure_with(lf, schema, rows, collect_streaming)
+    }
+
+    /// Counts the values of every column in a single query: one group-by per
+    /// column, all reading from the same scan. Each yields one row holding
+    /// the most frequent value in a column of its own, so values keep their
+    /// type.
+    fn measure_with(
+        &self,
+        lf: &LazyFrame,
+        schema: &Schema,
+        rows: usize,
+        collect: impl Fn(LazyFrame) -> PolarsResult<DataFrame>,
+    ) -> Result<ConstantColumns> {
+        let names: Vec<&PlSmallStr> = schema
+            .iter()
+            .filter(|(_, dtype)| !dtype.is_nested())
+            .map(|(name, _)| name)
+            .collect();
+        let mut columns = Vec::new();
+        if rows == 0 || names.is_empty() {
+            return Ok(ConstantColumns {
+                max_dominant_pct: self.max_dominant_pct,
+                columns,
+            });
+        }
+
+        let value_alias = |i: usize| format!("{}{}", VALUE_ALIAS, i);
+        let counts = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                lf.clone()
+                    .group_by([col((*name).clone()).alias(value_alias(i))])
+                    .agg([len().alias(COUNT_ALIAS)])
+                    .select([
+                        col(value_alias(i))
+                            .sort_by(
+                                [col(COUNT_ALIAS)],
+                                SortMultipleOptions::default().with_order_descending(true),
+                            )
+                            .first(),
+                        col(COUNT_ALIAS).max(),
+                        len().alias(UNIQUE_ALIAS),
+                    ])
+            })
+            .collect::<Vec<_>>();
+        let counts = collect(concat_lf_diagonal(counts, UnionArgs::default())?)?;
+
+        let count_column = counts.column(COUNT_ALIAS)?.cast(&DataType::UInt64)?;
+        let unique_column = counts.column(UNIQUE_ALIAS)?.cast(&DataType::UInt64)?;
+        for (i, name) in names.iter().enumerate() {
+            let unique = unique_column.u64()?.get(i).unwrap_or(0) as usize;
+            let count = count_column.u64()?.get(i).unwrap_or(0) as usize;
+            let share = percentage(count, rows);
+            if unique > 1 && share <= self.max_dominant_pct {
+                continue;
+            }
+
+            let value = counts.column(&value_alias(i))?.get(i)?;
+            columns.push(ConstantColumn {
+                column: name.to_string(),
+                value: (!value.is_null()).then(|| value.str_value().into_owned()),
+                count,
+                percentage: share,
+                unique,
+            });
+        }
+
+        Ok(ConstantColumns {
+            max_dominant_pct: self.max_dominant_pct,
+            columns,
+        })
+    }
+
+    pub fn findings(&self, constant: &ConstantColumns) -> Vec<Finding> {
+        constant
+            .columns
+            .iter()
+            .map(|c| Finding {
+                check: "constant".to_string(),
+                column: Some(c.column.clone()),
+                severity: Severity::Warn,
+                message: c.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl Check for ConstantColumnsCheck {
+    fn name(&self) -> &'static str {
+        "constant"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(self.findings(&self.measure(df)?))
+    }
+}
+
+/// Integer and text columns with nearly as many distinct values as rows, such
+/// as ids, UUIDs, timestamps stored as text or free text. They identify rows
+/// rather than describe them, so a model can only memorise them.
+#[derive(Debug, Clone)]
+pub struct IdentifierCheck {
+    /// Distinct values, as a percentage of non-null rows, above which a column
+    /// is flagged.
+    pub max_unique_pct: f64,
+    /// Never flagged, e.g. a regression target whose values are all distinct.
+    pub target: Option<String>,
+}
+
+impl Default for IdentifierCheck {
+    fn default() -> Self {
+        IdentifierCheck {
+            max_unique_pct: 95.0,
+            target: None,
+        }
+    }
+}
+
+impl IdentifierCheck {
+    pub fn measure(&self, df: &DataFrame) -> Result<Identifiers> {
+        identifiers::detect(df, self.max_unique_pct, self.target.as_deref())
+    }
+
+    /// Streaming counterpart of [`Self::measure`]; see
+    /// [`identifiers::detect_lazy`] for what it holds in memory.
+    pub fn measure_lazy(&self, lf: &LazyFrame, schema: &Schema) -> Result<Identifiers> {
+        identifiers::detect_lazy(lf, schema, self.max_unique_pct, self.target.as_deref())
+    }
+
+    pub fn findings(&self, identifiers: &Identifiers) -> Vec<Finding> {
+        identifiers
+            .columns
+            .iter()
+            .map(|c| Finding {
+                check: "identifiers".to_string(),
+                column: Some(c.column.clone()),
+                severity: Severity::Warn,
+                message: c.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl Check for IdentifierCheck {
+    fn name(&self) -> &'static str {
+        "identifiers"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(self.findings(&self.measure(df)?))
+    }
+}
+
+/// Presence, class balance or distribution of the target column, and
+/// features that leak it.
+pub struct TargetCheck {
+    pub column: String,
+    pub options: TargetOptions,
+    /// Majority/minority class ratio above which the target is an error.
+    pub max_imbalance_ratio: Option<f64>,
+}
+
+impl TargetCheck {
+    pub fn measure(&self, df: &DataFrame) -> Result<TargetReport> {
+        analyze_target(df, &self.column, &self.options)
+    }
+
+    pub fn findings(&self, target: &TargetReport) -> Vec<Finding> {
+        let finding = |severity, message| Finding {
+            check: "target".to_string(),
+            column: Some(target.column.clone()),
+            severity,
+            message,
+        };
+
+        if !target.found {
+            return vec![finding(
+                Severity::Error,
+                "target column not found".to_string(),
+            )];
+        }
+
+        let mut findings = Vec::new();
+
+        if let Some(missing) = target.missing.filter(|&m| m > 0) {
+            findings.push(finding(
+                Severity::Warn,
+                format!(
+                    "{} missing target values ({:.1}%)",
+                    missing,
+                    target.missing_percentage.unwrap_or(0.0)
+                ),
+            ));
+        }
+
+        if let Some(classes) = &target.classes {
+            if let Some(max) = self
+                .max_imbalance_ratio
+                .filter(|&max| classes.imbalance_ratio > max)
+            {
+                findings.push(finding(
+                    Severity::Error,
+                    format!(
+                        "imbalance ratio {:.2} exceeds {:.2}",
+                        classes.imbalance_ratio, max
+                    ),
+                ));
+            }
+            if !classes.rare_classes.is_empty() {
+                findings.push(finding(
+                    Severity::Warn,
+                    format!(
+                        "classes with fewer than {} rows: {}",
+                        classes.min_class_count,
+                        classes.rare_classes.join(", ")
+                    ),
+                ));
+            }
+            if !classes.empty_in_split.is_empty() {
+                findings.push(finding(
+                    Severity::Warn,
+                    format!(
+                        "classes left empty by a {:.0}% stratified split: {}",
+                        classes.test_size * 100.0,
+                        classes.empty_in_split.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        for suspect in target.leakage.iter().flatten() {
+            // Names are only a hint; the statistical rules are hard evidence.
+            let severity = if suspect.rule == "name" {
+                Severity::Warn
+            } else {
+                Severity::Error
+            };
+            findings.push(Finding {
+                check: "leakage".to_string(),
+                column: Some(suspect.column.clone()),
+                severity,
+                message: suspect.message.clone(),
+            });
+        }
+
+        if let Some(dist) = target
+            .distribution
+            .as_ref()
+            .filter(|d| d.skewness.abs() > 1.0)
+        {
+            findings.push(finding(
+                Severity::Info,
+                format!(
+                    "target is highly skewed ({:.2}); consider a log or rank transform",
+                    dist.skewness
+                ),
+            ));
+        }
+
+        findings
+    }
+}
+
+impl Check for TargetCheck {
+    fn name(&self) -> &'static str {
+        "target"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(self.findings(&self.measure(df)?))
+    }
+}
+
+/// Conformance to a YAML schema contract; every violation is an error.
+pub struct ContractCheck {
+    pub contract: Contract,
+    /// Where the contract was loaded from, echoed in the report.
+    pub path: String,
+}
+
+impl ContractCheck {
+    pub fn from_path(path: &str) -> Result<Self> {
+        Ok(ContractCheck {
+            contract: Contract::from_path(path)?,
+            path: path.to_string(),
+        })
+    }
+
+    pub fn measure(&self, df: &DataFrame) -> Result<ContractReport> {
+        self.contract.check(df, &self.path)
+    }
+
+    /// Every violation is an error, so unlike the other checks the findings
+    /// need nothing but the report.
+    pub fn findings(contract: &ContractReport) -> Vec<Finding> {
+        contract
+            .violations
+            .iter()
+            .map(|v| Finding {
+                check: "contract".to_string(),
+                column: Some(v.column.clone()),
+                severity: Severity::Error,
+                message: format!("{}: {}", v.rule, v.message),
+            })
+            .collect()
+    }
+}
+
+impl Check for ContractCheck {
+    fn name(&self) -> &'static str {
+        "contract"
+    }
+
+    fn run(&self, df: &DataFrame) -> Result<Vec<Finding>> {
+        Ok(Self::findings(&self.measure(df)?))
+    }
+}
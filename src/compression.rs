@@ -0,0 +1,181 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{Context, Result};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const BZIP2_MAGIC: &[u8] = b"BZh";
+const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
+/// Tells apart spills of equally named files within one run.
+static SPILLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    fn from_extension(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "gz" | "gzip" => Some(Compression::Gzip),
+            "zst" | "zstd" => Some(Compression::Zstd),
+            "bz2" => Some(Compression::Bzip2),
+            "xz" => Some(Compression::Xz),
+            _ => None,
+        }
+    }
+
+    /// Recognises compressed data from its first bytes.
+    pub fn from_magic(head: &[u8]) -> Option<Self> {
+        [
+            (GZIP_MAGIC, Compression::Gzip),
+            (ZSTD_MAGIC, Compression::Zstd),
+            (BZIP2_MAGIC, Compression::Bzip2),
+            (XZ_MAGIC, Compression::Xz),
+        ]
+        .into_iter()
+        .find(|(magic, _)| head.starts_with(magic))
+        .map(|(_, compression)| compression)
+    }
+
+    /// Detects compression from the extension, falling back to magic bytes.
+    /// Directories and missing files are reported as uncompressed.
+    pub fn detect(path: &str) -> Result<Option<Self>> {
+        let path = Path::new(path);
+        if let Some(compression) = Compression::from_extension(path) {
+            return Ok(Some(compression));
+        }
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let mut head = [0u8; 6];
+        let read = File::open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?
+            .read(&mut head)?;
+        Ok(Compression::from_magic(&head[..read]))
+    }
+
+    /// Opens `path` for reading, decompressing on the fly.
+    pub fn open(self, path: &str) -> Result<Box<dyn Read>> {
+        let file = File::open(path).with_context(|| format!("failed to open {}", path))?;
+        self.decoder(BufReader::new(file))
+    }
+
+    fn decoder<'a>(self, reader: impl BufRead + 'a) -> Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            // Multi-member, so files written by `pigz` or concatenated with
+            // `cat` are read to the end.
+            Compression::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+            Compression::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+            Compression::Bzip2 => Box::new(bzip2::bufread::MultiBzDecoder::new(reader)),
+            Compression::Xz => Box::new(liblzma::bufread::XzDecoder::new_multi_decoder(reader)),
+        })
+    }
+
+    /// Decompresses the whole file into memory.
+    pub fn read_to_end(self, path: &str) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.open(path)?
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed to decompress {} as {}", path, self))?;
+        Ok(bytes)
+    }
+
+    /// Decompresses `path` a buffer at a time into a file in `dir`, so the
+    /// data can be scanned lazily. Costs disk space for the uncompressed data
+    /// instead of memory.
+    pub fn spill(self, path: &str, dir: &Path) -> Result<Spill> {
+        let name = strip_extension(Path::new(path))
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let target = dir.join(format!(
+            "mlcheck-{}-{}-{}",
+            std::process::id(),
+            SPILLS.fetch_add(1, Ordering::Relaxed),
+            name
+        ));
+        let target = target
+            .to_str()
+            .context("spill directory path is not valid UTF-8")?
+            .to_string();
+
+        // Created before the guard, so a name clash never deletes a file
+        // that is not ours.
+        let file = File::options()
+            .write(true)
+            .create_new(true)
+            .open(&target)
+            .with_context(|| format!("failed to create {}", target))?;
+        let spill = Spill { path: target };
+        let mut out = BufWriter::new(file);
+        io::copy(&mut self.open(path)?, &mut out)
+            .and_then(|_| out.flush())
+            .with_context(|| {
+                format!(
+                    "failed to decompress {} as {} into {}",
+                    path,
+                    self,
+                    dir.display()
+                )
+            })?;
+        Ok(spill)
+    }
+
+    /// Decompresses data already in memory, e.g. piped through stdin.
+    pub fn decompress(self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.decoder(data)?
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed to decompress input as {}", self))?;
+        Ok(bytes)
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::Gzip => write!(f, "gzip"),
+            Compression::Zstd => write!(f, "zstd"),
+            Compression::Bzip2 => write!(f, "bzip2"),
+            Compression::Xz => write!(f, "xz"),
+        }
+    }
+}
+
+/// A decompressed copy of a file, removed on drop. A process killed before
+/// then leaves it behind.
+pub struct Spill {
+    path: String,
+}
+
+impl Spill {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The path without a compression extension, e.g. `train.csv` for
+/// `train.csv.gz`, so the data format can be read from what is left.
+pub fn strip_extension(path: &Path) -> PathBuf {
+    match Compression::from_extension(path) {
+        Some(_) => path.with_extension(""),
+        None => path.to_path_buf(),
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+use crate::check::CheckKind;
+use crate::dialect::CsvOptions;
+use crate::outliers::OutlierMethod;
+use crate::report::{InspectFormat, OutputFormat, Severity};
+use crate::target::{self, TargetTask};
+
+/// Read from the working directory when `--config` is not given.
+pub const CONFIG_FILE: &str = "mlcheck.toml";
+
+/// Project defaults from `mlcheck.toml`. Keys are named after the command-line
+/// flags, which take precedence over them, e.g.
+///
+/// ```toml
+/// ignore_columns = ["id"]
+///
+/// [csv]
+/// delimiter = ";"
+///
+/// [validate]
+/// target = "label"
+/// fail_on = "warn"
+/// max_missing_pct = 5.0
+/// checks = ["missing", "duplicates", "target"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Columns dropped before any check or profile, e.g. row ids. Names not
+    /// present in a file are ignored, so one list can cover many datasets.
+    pub ignore_columns: Vec<String>,
+    pub csv: CsvOptions,
+    pub validate: ValidateConfig,
+    pub inspect: InspectConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidateConfig {
+    pub target: Option<String>,
+    pub format: Option<OutputFormat>,
+    pub fail_on: Option<Severity>,
+    /// Checks to run; every check when absent.
+    pub checks: Option<Vec<CheckKind>>,
+    pub max_missing_pct: Option<f64>,
+    pub max_duplicate_pct: Option<f64>,
+    pub outlier_method: Option<OutlierMethod>,
+    pub outlier_threshold: Option<f64>,
+    pub max_outlier_pct: Option<f64>,
+    pub max_dominant_pct: Option<f64>,
+    pub max_unique_pct: Option<f64>,
+    pub task: Option<TargetTask>,
+    pub min_class_count: Option<usize>,
+    pub max_imbalance_ratio: Option<f64>,
+    pub test_size: Option<f64>,
+    /// Schema contract; a relative path is resolved against the directory of
+    /// the config file.
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InspectConfig {
+    pub format: Option<InspectFormat>,
+    pub profile: Option<bool>,
+    pub top_k: Option<usize>,
+}
+
+impl Config {
+    pub fn from_path(path: &str) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?;
+        let mut config: Config =
+            toml::from_str(&text).with_context(|| format!("invalid config {}", path))?;
+
+        if let Some(test_size) = config.validate.test_size {
+            target::check_test_size(test_size)
+                .map_err(|e| anyhow!("invalid config {}: {}", path, e))?;
+        }
+        if let Some(schema) = &mut config.validate.schema
+            && let Some(dir) = Path::new(path).parent()
+        {
+            *schema = dir.join(&*schema).to_string_lossy().into_owned();
+        }
+        Ok(config)
+    }
+
+    /// Loads `path` when given, otherwise [`CONFIG_FILE`] from the working
+    /// directory if there is one. Without either every setting is left unset.
+    pub fn discover(path: Option<&str>) -> Result<Self> {
+        match path {
+            Some(path) => Config::from_path(path),
+            None if Path::new(CONFIG_FILE).is_file() => Config::from_path(CONFIG_FILE),
+            None => Ok(Config::default()),
+        }
+    }
+}
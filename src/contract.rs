@@ -0,0 +1,416 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+
+use anyhow::{Context, Result};
+use polars::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How many distinct offending values are kept per violation.
+const MAX_EXAMPLES: usize = 5;
+
+/// Expected shape of a dataset, loaded from a YAML contract file.
+///
+/// ```yaml
+/// allow_extra_columns: false
+/// columns:
+///   - name: age
+///     dtype: integer
+///     nullable: false
+///     min: 0
+///     max: 120
+///   - name: city
+///     dtype: str
+///     allowed: [Jakarta, Bandung, Surabaya]
+///   - name: email
+///     pattern: "^[^@]+@[^@]+$"
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    #[serde(default = "default_true")]
+    pub allow_extra_columns: bool,
+    pub columns: Vec<ColumnContract>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnContract {
+    pub name: String,
+    /// Exact polars dtype (`i64`, `str`, `date`, ...) or a family: `integer`,
+    /// `float`, `numeric`, `string`, `boolean`, `temporal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtype: Option<String>,
+    #[serde(default = "default_true")]
+    pub required: bool,
+    #[serde(default = "default_true")]
+    pub nullable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractReport {
+    pub contract: String,
+    pub columns_checked: usize,
+    pub violations: Vec<Violation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    pub column: String,
+    pub rule: String,
+    pub message: String,
+    /// Number of offending rows, when the rule is checked row by row.
+    pub count: Option<usize>,
+    pub examples: Vec<String>,
+}
+
+impl Contract {
+    /// Builds a starter contract from what is observed in `df`: exact dtypes,
+    /// nullability, numeric ranges, and value sets for text columns with at
+    /// most `max_allowed_values` distinct values.
+    pub fn infer(df: &DataFrame, max_allowed_values: usize) -> Result<Self> {
+        let columns = df
+            .get_columns()
+            .iter()
+            .map(|col| ColumnContract::infer(col, max_allowed_values))
+            .collect::<Result<_>>()?;
+
+        Ok(Contract {
+            allow_extra_columns: false,
+            columns,
+        })
+    }
+
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Loads a contract and compiles its patterns, so a bad regex fails before
+    /// the dataset is read.
+    pub fn from_path(path: &str) -> Result<Self> {
+        let text = fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?;
+        let contract: Contract =
+            serde_yaml::from_str(&text).with_context(|| format!("invalid contract {}", path))?;
+        for spec in &contract.columns {
+            spec.regex()
+                .with_context(|| format!("invalid contract {}", path))?;
+        }
+        Ok(contract)
+    }
+
+    pub fn check(&self, df: &DataFrame, contract_path: &str) -> Result<ContractReport> {
+        let mut violations = Vec::new();
+
+        for spec in &self.columns {
+            match df.column(&spec.name) {
+                Ok(col) => violations.extend(spec.check(col)?),
+                Err(_) if spec.required => violations.push(Violation {
+                    column: spec.name.clone(),
+                    rule: "required".to_string(),
+                    message: "column is missing".to_string(),
+                    count: None,
+                    examples: Vec::new(),
+                }),
+                Err(_) => {}
+            }
+        }
+
+        if !self.allow_extra_columns {
+            let declared: HashSet<&str> = self.columns.iter().map(|c| c.name.as_str()).collect();
+            for col in df.get_columns() {
+                if !declared.contains(col.name().as_str()) {
+                    violations.push(Violation {
+                        column: col.name().to_string(),
+                        rule: "extra_column".to_string(),
+                        message: "column is not declared in the contract".to_string(),
+                        count: None,
+                        examples: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        Ok(ContractReport {
+            contract: contract_path.to_string(),
+            columns_checked: self.columns.len(),
+            violations,
+        })
+    }
+}
+
+impl ColumnContract {
+    fn infer(col: &Column, max_allowed_values: usize) -> Result<Self> {
+        let dtype = col.dtype();
+        let mut contract = ColumnContract {
+            name: col.name().to_string(),
+            dtype: Some(dtype.to_string()),
+            required: true,
+            nullable: col.null_count() > 0,
+            allowed: None,
+            min: None,
+            max: None,
+            pattern: None,
+        };
+
+        if dtype.is_primitive_numeric() {
+            let cast = col.cast(&DataType::Float64)?;
+            let values = cast.f64()?;
+            contract.min = values.min();
+            contract.max = values.max();
+        } else if dtype.is_string() || dtype.is_categorical() || dtype.is_bool() {
+            let cast = col.cast(&DataType::String)?;
+            let distinct: BTreeSet<&str> = cast.str()?.into_iter().flatten().collect();
+            if distinct.len() <= max_allowed_values {
+                contract.allowed = Some(
+                    distinct
+                        .into_iter()
+                        .map(|v| serde_yaml::Value::String(v.to_string()))
+                        .collect(),
+                );
+            }
+        }
+
+        Ok(contract)
+    }
+
+    fn check(&self, col: &Column) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+        let mut violation = |rule: &str, message: String, offending: Option<Offending>| {
+            let (count, examples) = match offending {
+                Some(o) => (Some(o.count), o.examples),
+                None => (None, Vec::new()),
+            };
+            violations.push(Violation {
+                column: self.name.clone(),
+                rule: rule.to_string(),
+                message,
+                count,
+                examples,
+            });
+        };
+
+        if let Some(expected) = &self.dtype
+            && !dtype_matches(col.dtype(), expected)
+        {
+            violation(
+                "dtype",
+                format!("expected {}, found {}", expected, col.dtype()),
+                None,
+            );
+        }
+
+        if !self.nullable && col.null_count() > 0 {
+            violation(
+                "nullable",
+                format!("{} null values in a non-nullable column", col.null_count()),
+                None,
+            );
+        }
+
+        if let Some(allowed) = &self.allowed {
+            // Numeric columns compare by value, so `1` in the contract matches
+            // `1.0` in a float column.
+            let offending = if col.dtype().is_primitive_numeric() {
+                let allowed: Vec<f64> = allowed.iter().filter_map(yaml_to_f64).collect();
+                offending_numbers(col, |v| !allowed.contains(&v))?
+            } else {
+                let allowed: HashSet<String> = allowed.iter().map(yaml_to_string).collect();
+                offending_strings(col, |v| !allowed.contains(v))?
+            };
+            if offending.count > 0 {
+                violation(
+                    "allowed",
+                    format!("{} values outside the allowed set", offending.count),
+                    Some(offending),
+                );
+            }
+        }
+
+        if (self.min.is_some() || self.max.is_some()) && !col.dtype().is_primitive_numeric() {
+            violation(
+                "range",
+                format!(
+                    "a range is declared, but the column is {}, not numeric",
+                    col.dtype()
+                ),
+                None,
+            );
+        } else if self.min.is_some() || self.max.is_some() {
+            let min = self.min.unwrap_or(f64::NEG_INFINITY);
+            let max = self.max.unwrap_or(f64::INFINITY);
+            let offending = offending_numbers(col, |v| v < min || v > max)?;
+            if offending.count > 0 {
+                violation(
+                    "range",
+                    format!(
+                        "{} values outside [{}, {}]",
+                        offending.count,
+                        self.min.map_or("-inf".to_string(), |v| v.to_string()),
+                        self.max.map_or("inf".to_string(), |v| v.to_string())
+                    ),
+                    Some(offending),
+                );
+            }
+        }
+
+        if let (Some(pattern), Some(regex)) = (&self.pattern, self.regex()?) {
+            let offending = offending_strings(col, |v| !regex.is_match(v))?;
+            if offending.count > 0 {
+                violation(
+                    "pattern",
+                    format!("{} values do not match /{}/", offending.count, pattern),
+                    Some(offending),
+                );
+            }
+        }
+
+        Ok(violations)
+    }
+
+    fn regex(&self) -> Result<Option<Regex>> {
+        self.pattern
+            .as_deref()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .with_context(|| format!("invalid pattern for column '{}'", self.name))
+            })
+            .transpose()
+    }
+}
+
+fn dtype_matches(actual: &DataType, expected: &str) -> bool {
+    match expected.to_ascii_lowercase().as_str() {
+        "integer" | "int" => actual.is_integer(),
+        "float" => actual.is_float(),
+        "numeric" => actual.is_primitive_numeric(),
+        "string" => actual.is_string() || actual.is_categorical(),
+        "boolean" => actual.is_bool(),
+        "temporal" => actual.is_temporal(),
+        exact => actual.to_string().eq_ignore_ascii_case(exact),
+    }
+}
+
+fn yaml_to_string(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+fn yaml_to_f64(value: &serde_yaml::Value) -> Option<f64> {
+    match value {
+        serde_yaml::Value::Number(n) => n.as_f64(),
+        serde_yaml::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+struct Offending {
+    count: usize,
+    examples: Vec<String>,
+}
+
+impl Offending {
+    fn collect<T: ToString>(values: impl Iterator<Item = T>) -> Self {
+        let mut count = 0;
+        let mut examples = BTreeSet::new();
+        for value in values {
+            count += 1;
+            if examples.len() < MAX_EXAMPLES {
+                examples.insert(value.to_string());
+            }
+        }
+        Offending {
+            count,
+            examples: examples.into_iter().collect(),
+        }
+    }
+}
+
+/// Non-null values rendered as strings that fail `is_offending`.
+fn offending_strings(col: &Column, is_offending: impl Fn(&str) -> bool) -> Result<Offending> {
+    let cast = col.cast(&DataType::String)?;
+    Ok(Offending::collect(
+        cast.str()?
+            .into_iter()
+            .flatten()
+            .filter(|v| is_offending(v)),
+    ))
+}
+
+fn offending_numbers(col: &Column, is_offending: impl Fn(f64) -> bool) -> Result<Offending> {
+    let cast = col.cast(&DataType::Float64)?;
+    Ok(Offending::collect(
+        cast.f64()?
+            .into_iter()
+            .flatten()
+            .filter(|v| is_offending(*v)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(yaml: &str) -> ColumnContract {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn rules(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn range_on_text_column_is_a_violation() {
+        let col = Column::new("age".into(), ["12", "40"]);
+        let violations = spec("{name: age, min: 0, max: 120}").check(&col).unwrap();
+        assert_eq!(rules(&violations), ["range"]);
+    }
+
+    #[test]
+    fn range_on_numeric_column_reports_offending_values() {
+        let col = Column::new("age".into(), [12i64, 140]);
+        let violations = spec("{name: age, min: 0, max: 120}").check(&col).unwrap();
+        assert_eq!(rules(&violations), ["range"]);
+        assert_eq!(violations[0].count, Some(1));
+    }
+
+    #[test]
+    fn allowed_numbers_compare_by_value() {
+        let col = Column::new("rating".into(), [1.0f64, 2.0, 3.5]);
+        let violations = spec("{name: rating, allowed: [1, 2]}").check(&col).unwrap();
+        assert_eq!(rules(&violations), ["allowed"]);
+        assert_eq!(violations[0].examples, ["3.5"]);
+    }
+
+    #[test]
+    fn invalid_pattern_fails_when_the_contract_is_loaded() {
+        let path =
+            std::env::temp_dir().join(format!("mlcheck-contract-{}.yaml", std::process::id()));
+        fs::write(
+            &path,
+            "columns:\n  - name: email\n    pattern: \"[unclosed\"\n",
+        )
+        .unwrap();
+        let error = Contract::from_path(path.to_str().unwrap()).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(format!("{:#}", error).contains("invalid pattern for column 'email'"));
+    }
+}
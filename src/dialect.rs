@@ -0,0 +1,258 @@
+use std::fs::File;
+use std::io::{Cursor, Read};
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use polars::prelude::*;
+use serde::Deserialize;
+
+/// Delimiters tried, in order of preference, when none is configured.
+const CANDIDATE_DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+/// Bytes read from the start of a file to detect its delimiter.
+const SNIFF_BYTES: usize = 64 * 1024;
+
+/// Lines compared when detecting the delimiter.
+const SNIFF_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Encoding {
+    Utf8,
+    /// UTF-8 with invalid bytes replaced instead of failing the read
+    Utf8Lossy,
+    /// ISO-8859-1, common in older spreadsheet exports
+    Latin1,
+}
+
+/// How CSV files are parsed; other formats ignore these.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CsvOptions {
+    /// `None` detects comma, tab, semicolon or pipe from the first lines.
+    pub delimiter: Option<char>,
+    pub has_header: bool,
+    pub quote_char: char,
+    /// Lines starting with this are skipped, e.g. `#`.
+    pub comment_prefix: Option<String>,
+    /// Values read as missing in addition to empty fields, e.g. `NA` or `NULL`.
+    pub null_values: Vec<String>,
+    /// Lines skipped before the header, e.g. a title or export banner.
+    pub skip_rows: usize,
+    pub encoding: Encoding,
+    /// Rows used to infer column types; `0` scans the whole file.
+    pub infer_schema_length: usize,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: None,
+            has_header: true,
+            quote_char: '"',
+            comment_prefix: None,
+            null_values: Vec::new(),
+            skip_rows: 0,
+            encoding: Encoding::Utf8,
+            infer_schema_length: 100,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// The configured delimiter, or the one detected from `head`, the first
+    /// [`SNIFF_BYTES`] of the file.
+    fn separator(&self, head: &[u8]) -> Result<u8> {
+        match self.delimiter {
+            Some(c) => ascii(c, "delimiter"),
+            None => Ok(self.sniff_delimiter(head)),
+        }
+    }
+
+    fn parse_options(&self, head: &[u8]) -> Result<CsvParseOptions> {
+        Ok(CsvParseOptions::default()
+            .with_separator(self.separator(head)?)
+            .with_quote_char(Some(ascii(self.quote_char, "quote character")?))
+            .with_comment_prefix(
+                self.comment_prefix
+                    .as_deref()
+                    .map(CommentPrefix::new_from_str),
+            )
+            .with_null_values(self.null_values())
+            .with_encoding(match self.encoding {
+                Encoding::Utf8 | Encoding::Latin1 => CsvEncoding::Utf8,
+                Encoding::Utf8Lossy => CsvEncoding::LossyUtf8,
+            }))
+    }
+
+    fn null_values(&self) -> Option<NullValues> {
+        if self.null_values.is_empty() {
+            return None;
+        }
+        Some(NullValues::AllColumns(
+            self.null_values.iter().map(|v| v.as_str().into()).collect(),
+        ))
+    }
+
+    fn infer_schema_length(&self) -> Option<usize> {
+        Some(self.infer_schema_length).filter(|&n| n > 0)
+    }
+
+    /// Picks the candidate that splits the first lines into the same, largest
+    /// number of fields. Falls back to a comma when none is consistent.
+    fn sniff_delimiter(&self, head: &[u8]) -> u8 {
+        let text = String::from_utf8_lossy(head);
+
+        let mut lines: Vec<&str> = text.lines().skip(self.skip_rows).collect();
+        if head.len() == SNIFF_BYTES {
+            // The last line is probably cut off.
+            lines.pop();
+        }
+        let lines: Vec<&str> = lines
+            .into_iter()
+            .filter(|line| !line.trim().is_empty())
+            .filter(|line| match &self.comment_prefix {
+                Some(prefix) => !line.starts_with(prefix.as_str()),
+                None => true,
+            })
+            .take(SNIFF_LINES)
+            .collect();
+
+        let quote = self.quote_char;
+        let best = CANDIDATE_DELIMITERS
+            .iter()
+            .filter_map(|&delimiter| {
+                let counts: Vec<usize> = lines
+                    .iter()
+                    .map(|line| count_unquoted(line, delimiter as char, quote))
+                    .collect();
+                let first = *counts.first()?;
+                (first > 0 && counts.iter().all(|&c| c == first)).then_some((delimiter, first))
+            })
+            // Earlier candidates win ties.
+            .rev()
+            .max_by_key(|&(_, count)| count);
+        best.map_or(b',', |(delimiter, _)| delimiter)
+    }
+
+    fn read_options(&self, head: &[u8]) -> Result<CsvReadOptions> {
+        Ok(CsvReadOptions::default()
+            .with_has_header(self.has_header)
+            .with_skip_rows(self.skip_rows)
+            .with_infer_schema_length(self.infer_schema_length())
+            .with_parse_options(self.parse_options(head)?))
+    }
+}
+
+fn count_unquoted(line: &str, delimiter: char, quote: char) -> usize {
+    let mut quoted = false;
+    let mut count = 0;
+    for c in line.chars() {
+        if c == quote {
+            quoted = !quoted;
+        } else if c == delimiter && !quoted {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn ascii(c: char, what: &str) -> Result<u8> {
+    if !c.is_ascii() {
+        bail!("CSV {} must be a single ASCII character", what);
+    }
+    Ok(c as u8)
+}
+
+/// Parses a delimiter or quote character; `tab` and `\t` stand for a tab.
+pub fn parse_char(s: &str) -> Result<char, String> {
+    if s == "tab" || s == "\\t" {
+        return Ok('\t');
+    }
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("expected a single character, got '{}'", s)),
+    }
+}
+
+pub fn read_csv(path: &str, csv: &CsvOptions) -> Result<DataFrame> {
+    if csv.encoding == Encoding::Latin1 {
+        let bytes = std::fs::read(path).with_context(|| format!("failed to read {}", path))?;
+        return read_csv_bytes(bytes, csv);
+    }
+    Ok(csv
+        .read_options(&read_head(path)?)?
+        .try_into_reader_with_file_path(Some(path.into()))?
+        .finish()?)
+}
+
+/// Parses CSV already in memory, e.g. a decompressed file.
+pub fn read_csv_bytes(bytes: Vec<u8>, csv: &CsvOptions) -> Result<DataFrame> {
+    let bytes = match csv.encoding {
+        // Every Latin-1 byte is the code point of the same value.
+        Encoding::Latin1 => bytes
+            .iter()
+            .map(|&b| b as char)
+            .collect::<String>()
+            .into_bytes(),
+        Encoding::Utf8 | Encoding::Utf8Lossy => bytes,
+    };
+    let options = csv.read_options(&bytes[..bytes.len().min(SNIFF_BYTES)])?;
+    Ok(options
+        .into_reader_with_file_handle(Cursor::new(bytes))
+        .finish()?)
+}
+
+fn read_head(path: &str) -> Result<Vec<u8>> {
+    let mut head = Vec::new();
+    File::open(path)
+        .with_context(|| format!("failed to open {}", path))?
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+pub fn scan_csv(path: &str, csv: &CsvOptions) -> Result<LazyFrame> {
+    if csv.encoding == Encoding::Latin1 {
+        bail!("latin1 encoding is not supported in streaming mode");
+    }
+    let parse = csv.parse_options(&read_head(path)?)?;
+    Ok(LazyCsvReader::new(PlPath::new(path))
+        .with_has_header(csv.has_header)
+        .with_skip_rows(csv.skip_rows)
+        .with_infer_schema_length(csv.infer_schema_length())
+        .with_separator(parse.separator)
+        .with_quote_char(parse.quote_char)
+        .with_comment_prefix(csv.comment_prefix.as_deref().map(Into::into))
+        .with_null_values(parse.null_values)
+        .with_encoding(parse.encoding)
+        .finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sniff(text: &str) -> char {
+        CsvOptions::default().sniff_delimiter(text.as_bytes()) as char
+    }
+
+    #[test]
+    fn sniffs_semicolon_tab_and_pipe() {
+        assert_eq!(sniff("a;b;c\n1;2,5;3\n4;5;6\n"), ';');
+        assert_eq!(sniff("a\tb\n1\t2\n3\t4\n"), '\t');
+        assert_eq!(sniff("a|b|c\n1|2|3\n"), '|');
+    }
+
+    #[test]
+    fn commas_inside_quotes_do_not_count() {
+        assert_eq!(count_unquoted(r#"1,"Smith, John",x"#, ',', '"'), 2);
+        assert_eq!(sniff("id;name\n1;\"Smith, John\"\n2;\"Doe, Jane\"\n"), ';');
+    }
+
+    #[test]
+    fn single_column_falls_back_to_comma() {
+        assert_eq!(sniff("name\nalice\nbob\n"), ',');
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use polars::prelude::*;
+use serde::Serialize;
+
+use crate::report::print_load_warnings;
+use crate::stats;
+
+#[derive(Debug, Serialize)]
+pub struct DriftReport {
+    pub schema_version: u32,
+    pub train: String,
+    pub test: String,
+    pub warnings: Vec<String>,
+    pub schema_mismatches: Vec<SchemaMismatch>,
+    /// Shared columns, drifted ones first, each group by descending score.
+    pub columns: Vec<ColumnDrift>,
+    /// Shared nested columns (lists, structs), which have no distribution
+    /// to compare.
+    pub not_compared: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SchemaMismatch {
+    pub column: String,
+    pub train_dtype: Option<String>,
+    pub test_dtype: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnDrift {
+    pub column: String,
+    /// KS statistic for numeric columns, Jensen-Shannon distance for
+    /// categorical ones; both lie in `[0, 1]` so columns can be ranked together.
+    pub score: f64,
+    /// PSI at or above its threshold for numeric columns, unlike `score`,
+    /// since PSI is unbounded; JS distance at or above its threshold for
+    /// categorical ones.
+    pub drifted: bool,
+    #[serde(flatten)]
+    pub stats: DriftStats,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum DriftStats {
+    Numeric {
+        ks_statistic: f64,
+        ks_p_value: f64,
+        psi: f64,
+    },
+    Categorical {
+        chi_square: f64,
+        chi_square_p_value: f64,
+        js_distance: f64,
+    },
+}
+
+pub struct DriftThresholds {
+    pub psi: f64,
+    pub js_distance: f64,
+    pub bins: usize,
+}
+
+/// Compares every column of `train` against `test`, returning schema
+/// mismatches, per-column drift (drifted columns first, then by score), and
+/// the nested columns that were not compared.
+pub fn compare(
+    train: &DataFrame,
+    test: &DataFrame,
+    thresholds: &DriftThresholds,
+) -> Result<(Vec<SchemaMismatch>, Vec<ColumnDrift>, Vec<String>)> {
+    let mut mismatches = Vec::new();
+    let mut columns = Vec::new();
+    let mut not_compared = Vec::new();
+
+    for train_col in train.get_columns() {
+        let name = train_col.name();
+        let Ok(test_col) = test.column(name) else {
+            mismatches.push(SchemaMismatch {
+                column: name.to_string(),
+                train_dtype: Some(train_col.dtype().to_string()),
+                test_dtype: None,
+            });
+            continue;
+        };
+
+        if train_col.dtype() != test_col.dtype() {
+            mismatches.push(SchemaMismatch {
+                column: name.to_string(),
+                train_dtype: Some(train_col.dtype().to_string()),
+                test_dtype: Some(test_col.dtype().to_string()),
+            });
+        }
+
+        if train_col.dtype().is_nested() || test_col.dtype().is_nested() {
+            not_compared.push(name.to_string());
+            continue;
+        }
+        let drift = match (is_numeric(train_col.dtype()), is_numeric(test_col.dtype())) {
+            (true, true) => numeric_drift(train_col, test_col, thresholds)?,
+            (false, false) => categorical_drift(train_col, test_col, thresholds)?,
+            // A numeric column turned into strings (or back) can't be compared
+            // meaningfully; the dtype mismatch above already reports it.
+            _ => continue,
+        };
+        columns.push(drift);
+    }
+
+    for test_col in test.get_columns() {
+        if train.column(test_col.name()).is_err() {
+            mismatches.push(SchemaMismatch {
+                column: test_col.name().to_string(),
+                train_dtype: None,
+                test_dtype: Some(test_col.dtype().to_string()),
+            });
+        }
+    }
+
+    // Numeric columns are flagged by PSI but scored by KS, so a flagged
+    // column can score below an unflagged one; flags go first either way.
+    columns.sort_by(|a, b| b.drifted.cmp(&a.drifted).then(b.score.total_cmp(&a.score)));
+    Ok((mismatches, columns, not_compared))
+}
+
+fn is_numeric(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric() || dtype.is_temporal()
+}
+
+fn sorted_values(col: &Column) -> Result<Vec<f64>> {
+    let col = col.to_physical_repr().cast(&DataType::Float64)?;
+    let mut values: Vec<f64> = col
+        .f64()?
+        .into_iter()
+        .flatten()
+        .filter(|v| !v.is_nan())
+        .collect();
+    values.sort_by(f64::total_cmp);
+    Ok(values)
+}
+
+fn numeric_drift(
+    train: &Column,
+    test: &Column,
+    thresholds: &DriftThresholds,
+) -> Result<ColumnDrift> {
+    let train_values = sorted_values(train)?;
+    let test_values = sorted_values(test)?;
+
+    let ks = stats::ks_statistic(&train_values, &test_values);
+    let psi = stats::psi(&train_values, &test_values, thresholds.bins);
+
+    Ok(ColumnDrift {
+        column: train.name().to_string(),
+        score: ks,
+        drifted: psi >= thresholds.psi,
+        stats: DriftStats::Numeric {
+            ks_statistic: ks,
+            ks_p_value: stats::ks_p_value(ks, train_values.len(), test_values.len()),
+            psi,
+        },
+    })
+}
+
+/// Counts every value (nulls included, since a change in missingness is drift
+/// too) as a string category.
+fn category_counts(col: &Column) -> Result<HashMap<Option<String>, usize>> {
+    let col = col.cast(&DataType::String)?;
+    let mut counts = HashMap::new();
+    for value in col.str()?.into_iter() {
+        *counts.entry(value.map(str::to_string)).or_default() += 1;
+    }
+    Ok(counts)
+}
+
+fn categorical_drift(
+    train: &Column,
+    test: &Column,
+    thresholds: &DriftThresholds,
+) -> Result<ColumnDrift> {
+    let train_counts = category_counts(train)?;
+    let test_counts = category_counts(test)?;
+
+    let categories: BTreeSet<&Option<String>> =
+        train_counts.keys().chain(test_counts.keys()).collect();
+    let a: Vec<usize> = categories
+        .iter()
+        .map(|c| train_counts.get(*c).copied().unwrap_or(0))
+        .collect();
+    let b: Vec<usize> = categories
+        .iter()
+        .map(|c| test_counts.get(*c).copied().unwrap_or(0))
+        .collect();
+
+    let (chi_square, dof) = stats::chi_square(&a, &b);
+    let js_distance = stats::js_divergence(&a, &b).sqrt();
+
+    Ok(ColumnDrift {
+        column: train.name().to_string(),
+        score: js_distance,
+        drifted: js_distance >= thresholds.js_distance,
+        stats: DriftStats::Categorical {
+            chi_square,
+            chi_square_p_value: stats::chi_square_p_value(chi_square, dof),
+            js_distance,
+        },
+    })
+}
+
+/// Formats of the drift report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DriftFormat {
+    Text,
+    Json,
+}
+
+impl DriftReport {
+    pub fn print(&self, format: DriftFormat) -> Result<()> {
+        match format {
+            DriftFormat::Text => self.print_text(),
+            DriftFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+        Ok(())
+    }
+
+    fn print_text(&self) {
+        println!("🔄 Drift: {} → {}\n", self.train, self.test);
+        print_load_warnings(&self.warnings);
+
+        println!("🧬 Schema:");
+        if self.schema_mismatches.is_empty() {
+            println!("└─ ✓ Both files share the same columns and dtypes");
+        }
+        for mismatch in &self.schema_mismatches {
+            match (&mismatch.train_dtype, &mismatch.test_dtype) {
+                (Some(train), Some(test)) => {
+                    println!("├─ ⚠️  {}: {} → {}", mismatch.column, train, test)
+                }
+                (Some(_), None) => println!("├─ ❌ {}: missing in test", mismatch.column),
+                (None, Some(_)) => println!("├─ ❌ {}: missing in train", mismatch.column),
+                (None, None) => {}
+            }
+        }
+
+        println!(
+            "\n📈 Column Drift (drifted first; numeric columns flagged by PSI, ranked by KS):"
+        );
+        for column in &self.not_compared {
+            println!("├─ ➖ {}: nested column, not compared", column);
+        }
+        if self.columns.is_empty() {
+            println!("└─ No comparable columns");
+            return;
+        }
+        for col in &self.columns {
+            let icon = if col.drifted { "⚠️ " } else { "✓" };
+            match &col.stats {
+                DriftStats::Numeric {
+                    ks_statistic,
+                    ks_p_value,
+                    psi,
+                } => println!(
+                    "├─ {} {}: KS={:.3} (p={:.3}), PSI={:.3}",
+                    icon, col.column, ks_statistic, ks_p_value, psi
+                ),
+                DriftStats::Categorical {
+                    chi_square,
+                    chi_square_p_value,
+                    js_distance,
+                } => println!(
+                    "├─ {} {}: χ²={:.2} (p={:.3}), JS={:.3}",
+                    icon, col.column, chi_square, chi_square_p_value, js_distance
+                ),
+            }
+        }
+
+        let drifted = self.columns.iter().filter(|c| c.drifted).count();
+        println!("└─ {} of {} columns drifted", drifted, self.columns.len());
+    }
+}
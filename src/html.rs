@@ -0,0 +1,640 @@
+use anyhow::Result;
+use polars::prelude::*;
+
+use crate::check::CheckKind;
+use crate::profile::{ColumnProfile, ProfileStats};
+use crate::report::{
+    InspectReport, Overview, Severity, ValidationReport, format_percentage, format_quantiles,
+};
+use crate::sample::SampleInfo;
+use crate::stats::HistogramBin;
+use crate::target::{ClassBalance, TargetReport};
+
+/// Rows are grouped into at most this many bands in the missing-value map.
+const MAX_ROW_BANDS: usize = 40;
+
+/// Bars shown in a class or top-value chart.
+const MAX_BARS: usize = 20;
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2rem auto; max-width: 1100px; color: #24292f; padding: 0 1rem; }
+h1 { font-size: 1.6rem; margin-bottom: 0.2rem; }
+h2 { font-size: 1.2rem; border-bottom: 1px solid #d0d7de; padding-bottom: 0.3rem; margin-top: 2rem; }
+table { border-collapse: collapse; margin: 0.5rem 0; }
+th, td { border: 1px solid #d0d7de; padding: 0.3rem 0.6rem; text-align: left; font-size: 0.9rem; }
+th { background: #f6f8fa; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+.muted { color: #57606a; font-size: 0.9rem; }
+.badge { display: inline-block; padding: 0.1rem 0.5rem; border-radius: 1rem; font-size: 0.8rem; font-weight: 600; color: #fff; }
+.pass { background: #1a7f37; } .fail { background: #cf222e; }
+.error { background: #cf222e; } .warn { background: #bf8700; } .info { background: #0969da; }
+.note { background: #fff8c5; border: 1px solid #d4a72c; padding: 0.5rem 0.8rem; border-radius: 6px; }
+.cards { display: grid; grid-template-columns: repeat(auto-fill, minmax(320px, 1fr)); gap: 1rem; }
+.card { border: 1px solid #d0d7de; border-radius: 6px; padding: 0.8rem; }
+.card h3 { font-size: 1rem; margin: 0 0 0.3rem; word-break: break-all; }
+svg text { font-size: 10px; fill: #24292f; }
+";
+
+/// Share of missing values per column for consecutive bands of rows, so
+/// missingness that clusters in part of the file stands out.
+#[derive(Debug)]
+pub struct MissingMap {
+    pub columns: Vec<String>,
+    pub rows: usize,
+    /// `cells[band][column]`, each in `[0, 1]`.
+    pub cells: Vec<Vec<f64>>,
+}
+
+impl MissingMap {
+    pub fn from_frame(df: &DataFrame) -> Result<Self> {
+        let rows = df.height();
+        let bands = rows.clamp(1, MAX_ROW_BANDS);
+        let band_rows = rows.div_ceil(bands).max(1);
+        let mut cells = vec![vec![0.0; df.width()]; rows.div_ceil(band_rows)];
+
+        for (c, col) in df.get_columns().iter().enumerate() {
+            if col.null_count() == 0 {
+                continue;
+            }
+            for (row, is_null) in col.is_null().into_no_null_iter().enumerate() {
+                if is_null {
+                    cells[row / band_rows][c] += 1.0;
+                }
+            }
+        }
+        for (band, row) in cells.iter_mut().enumerate() {
+            let size = band_rows.min(rows - band * band_rows) as f64;
+            for cell in row.iter_mut() {
+                *cell /= size;
+            }
+        }
+
+        Ok(MissingMap {
+            columns: df
+                .get_column_names()
+                .iter()
+                .map(|n| n.to_string())
+                .collect(),
+            rows,
+            cells,
+        })
+    }
+}
+
+pub fn validation_page(report: &ValidationReport) -> String {
+    let mut body = String::new();
+
+    let status = if report.passed {
+        "<span class=\"badge pass\">passed</span>"
+    } else {
+        "<span class=\"badge fail\">failed</span>"
+    };
+    body.push_str(&format!(
+        "<h1>Validation report: {}</h1>\n<p class=\"muted\">{} &middot; fail-on: {} &middot; report schema v{}</p>\n",
+        escape(&report.file),
+        status,
+        report.fail_on,
+        report.schema_version
+    ));
+    if let Some(sample) = &report.sample {
+        body.push_str(&sample_note(sample));
+    }
+    body.push_str(&warnings_section(&report.warnings));
+    body.push_str(&overview_section(
+        &report.overview,
+        report.execution.streaming,
+    ));
+    if let Some(spilled) = &report.execution.spilled {
+        body.push_str(&format!(
+            "<p class=\"muted\">Streaming {}.</p>\n",
+            escape(&spilled.to_string())
+        ));
+    }
+
+    body.push_str("<h2>Findings</h2>\n");
+    if report.findings.is_empty() {
+        body.push_str("<p>No findings.</p>\n");
+    } else {
+        body.push_str(
+            "<table><tr><th>Severity</th><th>Check</th><th>Column</th><th>Message</th></tr>\n",
+        );
+        for finding in &report.findings {
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                severity_badge(finding.severity),
+                escape(&finding.check),
+                escape(finding.column.as_deref().unwrap_or("")),
+                escape(&finding.message)
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+
+    body.push_str("<h2>Missing values</h2>\n");
+    match &report.missing {
+        None => body.push_str(&format!(
+            "<p>{}.</p>\n",
+            report.skip_reason(CheckKind::Missing)
+        )),
+        Some(missing) if missing.iter().all(|m| m.count == 0) => {
+            body.push_str("<p>No missing values.</p>\n")
+        }
+        Some(missing) => {
+            body.push_str("<table><tr><th>Column</th><th>Missing</th><th>%</th></tr>\n");
+            for m in missing.iter().filter(|m| m.count > 0) {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+                    escape(&m.column),
+                    m.count,
+                    format_percentage(m.percentage, m.ci.as_ref())
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+    }
+    if let Some(map) = &report.missing_map {
+        body.push_str(&missing_heatmap(map));
+    }
+
+    body.push_str("<h2>Duplicates</h2>\n");
+    match &report.duplicates {
+        Some(duplicates) => body.push_str(&format!("<p>{}</p>\n", duplicates)),
+        None => body.push_str(&format!(
+            "<p>{}.</p>\n",
+            report.skip_reason(CheckKind::Duplicates)
+        )),
+    }
+
+    body.push_str("<h2>Outliers</h2>\n");
+    match &report.outliers {
+        None => body.push_str(&format!(
+            "<p>{}.</p>\n",
+            report.skip_reason(CheckKind::Outliers)
+        )),
+        Some(outliers) => {
+            body.push_str(&format!(
+                "<p class=\"muted\">{}, k={}</p>\n",
+                outliers.method, outliers.threshold
+            ));
+            let flagged: Vec<_> = outliers.columns.iter().filter(|c| c.count > 0).collect();
+            if flagged.is_empty() {
+                body.push_str("<p>No outliers.</p>\n");
+            } else {
+                body.push_str("<table><tr><th>Column</th><th>Outliers</th><th>%</th><th>Accepted range</th><th>Example rows</th></tr>\n");
+                for c in flagged {
+                    body.push_str(&format!(
+                        "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td><td>[{:.3}, {:.3}]</td><td>{}</td></tr>\n",
+                        escape(&c.column),
+                        c.count,
+                        c.percentage,
+                        c.lower,
+                        c.upper,
+                        c.examples
+                            .iter()
+                            .map(|i| i.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+        }
+    }
+
+    body.push_str("<h2>Constant columns</h2>\n");
+    match &report.constant {
+        None => body.push_str(&format!(
+            "<p>{}.</p>\n",
+            report.skip_reason(CheckKind::Constant)
+        )),
+        Some(constant) if constant.columns.is_empty() => {
+            body.push_str("<p>No constant columns.</p>\n")
+        }
+        Some(constant) => {
+            body.push_str(&format!(
+                "<p class=\"muted\">dominant value &gt; {}%</p>\n",
+                constant.max_dominant_pct
+            ));
+            body.push_str("<table><tr><th>Column</th><th>Dominant value</th><th>Rows</th><th>%</th><th>Distinct</th></tr>\n");
+            for c in &constant.columns {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">{}</td></tr>\n",
+                    escape(&c.column),
+                    escape(c.value.as_deref().unwrap_or("null")),
+                    c.count,
+                    c.percentage,
+                    c.unique
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+    }
+
+    body.push_str("<h2>ID-like columns</h2>\n");
+    match &report.identifiers {
+        None => body.push_str(&format!(
+            "<p>{}.</p>\n",
+            report.skip_reason(CheckKind::Identifiers)
+        )),
+        Some(identifiers) if identifiers.columns.is_empty() => {
+            body.push_str("<p>No ID-like columns.</p>\n")
+        }
+        Some(identifiers) => {
+            body.push_str(&format!(
+                "<p class=\"muted\">distinct values &gt; {}% of rows; exclude these from features</p>\n",
+                identifiers.max_unique_pct
+            ));
+            body.push_str(
+                "<table><tr><th>Column</th><th>Looks like</th><th>Distinct</th><th>%</th></tr>\n",
+            );
+            for c in &identifiers.columns {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td></tr>\n",
+                    escape(&c.column),
+                    c.kind,
+                    c.unique,
+                    c.percentage
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+    }
+
+    if let Some(target) = &report.target {
+        body.push_str(&target_section(target));
+    }
+
+    if let Some(contract) = &report.contract {
+        body.push_str(&format!(
+            "<h2>Schema contract</h2>\n<p class=\"muted\">{} &middot; {} declared columns</p>\n",
+            escape(&contract.contract),
+            contract.columns_checked
+        ));
+        if contract.violations.is_empty() {
+            body.push_str("<p>All declared columns match the contract.</p>\n");
+        } else {
+            body.push_str(
+                "<table><tr><th>Column</th><th>Rule</th><th>Message</th><th>Examples</th></tr>\n",
+            );
+            for v in &contract.violations {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape(&v.column),
+                    escape(&v.rule),
+                    escape(&v.message),
+                    escape(&v.examples.join(", "))
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+    }
+
+    if !report.profiles.is_empty() {
+        body.push_str(&columns_section(&report.profiles));
+    }
+
+    page(&format!("mlcheck: {}", report.file), &body)
+}
+
+pub fn inspect_page(report: &InspectReport) -> String {
+    let mut body = format!("<h1>Dataset: {}</h1>\n", escape(&report.file));
+    body.push_str(&format!(
+        "<p class=\"muted\">{} &middot; report schema v{}</p>\n",
+        escape(&report.format),
+        report.schema_version
+    ));
+    if let Some(sample) = &report.sample {
+        body.push_str(&sample_note(sample));
+    }
+    body.push_str(&warnings_section(&report.warnings));
+    body.push_str(&overview_section(&report.overview, false));
+
+    if let Some(map) = &report.missing_map {
+        body.push_str("<h2>Missing values</h2>\n");
+        body.push_str(&missing_heatmap(map));
+    }
+    body.push_str(&columns_section(&report.columns));
+
+    page(&format!("mlcheck: {}", report.file), &body)
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}<p class=\"muted\">Generated by mlcheck {}</p>\n</body>\n</html>",
+        escape(title),
+        STYLE,
+        body,
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Escapes text for HTML or XML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn severity_badge(severity: Severity) -> String {
+    format!("<span class=\"badge {0}\">{0}</span>", severity)
+}
+
+fn sample_note(sample: &SampleInfo) -> String {
+    format!("<p class=\"note\">{}</p>\n", escape(&sample.to_string()))
+}
+
+fn warnings_section(warnings: &[String]) -> String {
+    if warnings.is_empty() {
+        return String::new();
+    }
+    let items: String = warnings
+        .iter()
+        .map(|w| format!("<li>{}</li>", escape(w)))
+        .collect();
+    format!("<h2>Schema warnings</h2>\n<ul>{}</ul>\n", items)
+}
+
+fn overview_section(overview: &Overview, streaming: bool) -> String {
+    let size_label = if streaming { "Size on disk" } else { "Size" };
+    format!(
+        "<h2>Overview</h2>\n<table><tr><th>Rows</th><td class=\"num\">{}</td></tr><tr><th>Columns</th><td class=\"num\">{}</td></tr><tr><th>{}</th><td class=\"num\">{:.2} MB</td></tr></table>\n",
+        overview.rows,
+        overview.columns,
+        size_label,
+        overview.size_bytes as f64 / 1_000_000.0
+    )
+}
+
+fn target_section(target: &TargetReport) -> String {
+    let mut html = format!("<h2>Target: {}</h2>\n", escape(&target.column));
+    if !target.found {
+        html.push_str("<p>Target column not found.</p>\n");
+        return html;
+    }
+
+    html.push_str(&format!(
+        "<table><tr><th>Type</th><td>{}</td></tr><tr><th>Unique values</th><td class=\"num\">{}</td></tr><tr><th>Missing</th><td class=\"num\">{} ({:.1}%)</td></tr></table>\n",
+        escape(target.dtype.as_deref().unwrap_or("?")),
+        target.unique.unwrap_or(0),
+        target.missing.unwrap_or(0),
+        target.missing_percentage.unwrap_or(0.0)
+    ));
+
+    if let Some(classes) = &target.classes {
+        html.push_str(&class_section(classes));
+    } else if let Some(dist) = &target.distribution {
+        html.push_str(&format!(
+            "<p>Regression target: range [{:.3}, {:.3}], mean {:.3} (std {:.3}), skewness {:.3}</p>\n<p class=\"muted\">{}</p>\n",
+            dist.min,
+            dist.max,
+            dist.mean,
+            dist.std,
+            dist.skewness,
+            format_quantiles(&dist.quantiles)
+        ));
+    }
+
+    if let Some(suspects) = &target.leakage {
+        html.push_str("<h3>Target leakage</h3>\n");
+        if suspects.is_empty() {
+            html.push_str("<p>No feature gives the target away.</p>\n");
+        } else {
+            html.push_str("<table><tr><th>Column</th><th>Rule</th><th>Message</th></tr>\n");
+            for s in suspects {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape(&s.column),
+                    escape(&s.rule),
+                    escape(&s.message)
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+    }
+    html
+}
+
+fn class_section(classes: &ClassBalance) -> String {
+    let bars: Vec<(String, usize)> = classes
+        .counts
+        .iter()
+        .map(|c| (c.class.clone(), c.count))
+        .collect();
+    let mut html = format!(
+        "<h3>Class distribution</h3>\n<p class=\"muted\">Imbalance ratio {:.2}</p>\n{}",
+        classes.imbalance_ratio,
+        bar_chart(&bars)
+    );
+    if !classes.rare_classes.is_empty() {
+        html.push_str(&format!(
+            "<p>Classes with fewer than {} rows: {}</p>\n",
+            classes.min_class_count,
+            escape(&classes.rare_classes.join(", "))
+        ));
+    }
+    if !classes.empty_in_split.is_empty() {
+        html.push_str(&format!(
+            "<p>Empty after a {:.0}% stratified split: {}</p>\n",
+            classes.test_size * 100.0,
+            escape(&classes.empty_in_split.join(", "))
+        ));
+    }
+    html
+}
+
+fn columns_section(profiles: &[ColumnProfile]) -> String {
+    let mut html = String::from("<h2>Columns</h2>\n<div class=\"cards\">\n");
+    for profile in profiles {
+        html.push_str(&format!(
+            "<div class=\"card\"><h3>{}</h3><p class=\"muted\">{} &middot; {} missing ({:.1}%)</p>\n",
+            escape(&profile.column),
+            escape(&profile.dtype),
+            profile.missing,
+            profile.missing_percentage
+        ));
+        match &profile.stats {
+            ProfileStats::Numeric {
+                min,
+                max,
+                mean,
+                std,
+                histogram: bins,
+                ..
+            } => {
+                let fmt = |v: &Option<f64>| v.map_or("-".to_string(), |v| format!("{:.3}", v));
+                html.push_str(&format!(
+                    "<p class=\"muted\">range [{}, {}], mean {} (std {})</p>\n{}",
+                    fmt(min),
+                    fmt(max),
+                    fmt(mean),
+                    fmt(std),
+                    histogram(bins)
+                ));
+            }
+            ProfileStats::Text {
+                cardinality,
+                empty_strings,
+                top_values,
+            } => {
+                let bars: Vec<(String, usize)> = top_values
+                    .iter()
+                    .map(|v| (v.value.clone(), v.count))
+                    .collect();
+                html.push_str(&format!(
+                    "<p class=\"muted\">{} distinct, {} empty strings</p>\n{}",
+                    cardinality,
+                    empty_strings,
+                    bar_chart(&bars)
+                ));
+            }
+            ProfileStats::Temporal { min, max } => {
+                html.push_str(&format!(
+                    "<p class=\"muted\">{} &rarr; {}</p>\n",
+                    escape(min.as_deref().unwrap_or("-")),
+                    escape(max.as_deref().unwrap_or("-"))
+                ));
+            }
+            ProfileStats::Other => {}
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+/// Vertical bars, one per histogram bin, with the range in a tooltip.
+fn histogram(bins: &[HistogramBin]) -> String {
+    let Some(max) = bins.iter().map(|b| b.count).max().filter(|&m| m > 0) else {
+        return String::new();
+    };
+    let (width, height) = (300.0, 100.0);
+    let bar_width = width / bins.len() as f64;
+
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" role=\"img\">",
+        width,
+        height + 14.0
+    );
+    for (i, bin) in bins.iter().enumerate() {
+        let bar_height = height * bin.count as f64 / max as f64;
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#0969da\"><title>[{:.3}, {:.3}): {}</title></rect>",
+            i as f64 * bar_width + 0.5,
+            height - bar_height,
+            (bar_width - 1.0).max(1.0),
+            bar_height,
+            bin.lower,
+            bin.upper,
+            bin.count
+        ));
+    }
+    if let (Some(first), Some(last)) = (bins.first(), bins.last()) {
+        svg.push_str(&format!(
+            "<text x=\"0\" y=\"{0}\">{1:.3}</text><text x=\"{2}\" y=\"{0}\" text-anchor=\"end\">{3:.3}</text>",
+            height + 12.0,
+            first.lower,
+            width,
+            last.upper
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Horizontal bars with labels, largest first as given.
+fn bar_chart(bars: &[(String, usize)]) -> String {
+    let Some(max) = bars.iter().map(|b| b.1).max().filter(|&m| m > 0) else {
+        return String::new();
+    };
+    let shown = &bars[..bars.len().min(MAX_BARS)];
+    let (label_width, bar_area, row_height) = (110.0, 160.0, 16.0);
+
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" role=\"img\">",
+        label_width + bar_area + 50.0,
+        row_height * shown.len() as f64
+    );
+    for (i, (label, count)) in shown.iter().enumerate() {
+        let y = i as f64 * row_height;
+        let short: String = label.chars().take(16).collect();
+        svg.push_str(&format!(
+            "<text x=\"{0}\" y=\"{1:.1}\" text-anchor=\"end\">{2}<title>{3}</title></text><rect x=\"{4}\" y=\"{5:.1}\" width=\"{6:.1}\" height=\"{7}\" fill=\"#8250df\"/><text x=\"{8:.1}\" y=\"{1:.1}\">{9}</text>",
+            label_width - 4.0,
+            y + 11.0,
+            escape(&short),
+            escape(label),
+            label_width,
+            y + 2.0,
+            (bar_area * *count as f64 / max as f64).max(1.0),
+            row_height - 4.0,
+            label_width + bar_area * *count as f64 / max as f64 + 4.0,
+            count
+        ));
+    }
+    svg.push_str("</svg>\n");
+    if bars.len() > shown.len() {
+        svg.push_str(&format!(
+            "<p class=\"muted\">… {} more</p>\n",
+            bars.len() - shown.len()
+        ));
+    }
+    svg
+}
+
+/// One column per dataset column, one row per band of rows; darker cells
+/// hold more missing values.
+fn missing_heatmap(map: &MissingMap) -> String {
+    if map.columns.is_empty() || map.cells.is_empty() {
+        return String::new();
+    }
+    let cell_width = (800.0 / map.columns.len() as f64).clamp(6.0, 40.0);
+    let cell_height = 8.0;
+    let header = 90.0;
+    let band_rows = map.rows.div_ceil(map.cells.len()).max(1);
+
+    let mut svg = format!(
+        "<p class=\"muted\">Rows top to bottom in {} bands of up to {} rows; darker means more missing.</p>\n<svg width=\"{}\" height=\"{}\" role=\"img\">",
+        map.cells.len(),
+        band_rows,
+        cell_width * map.columns.len() as f64 + 10.0,
+        header + cell_height * map.cells.len() as f64
+    );
+    for (c, name) in map.columns.iter().enumerate() {
+        let x = c as f64 * cell_width + cell_width / 2.0;
+        let short: String = name.chars().take(14).collect();
+        svg.push_str(&format!(
+            "<text transform=\"translate({:.1},{}) rotate(-60)\">{}</text>",
+            x,
+            header - 4.0,
+            escape(&short)
+        ));
+    }
+    for (band, row) in map.cells.iter().enumerate() {
+        for (c, &share) in row.iter().enumerate() {
+            let start = band * band_rows;
+            svg.push_str(&format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{}\" fill=\"rgba(207,34,46,{:.3})\" stroke=\"#eaeef2\" stroke-width=\"0.5\"><title>{}, rows {}-{}: {:.1}% missing</title></rect>",
+                c as f64 * cell_width,
+                header + band as f64 * cell_height,
+                cell_width,
+                cell_height,
+                share.max(0.02),
+                escape(&map.columns[c]),
+                start,
+                (start + band_rows).min(map.rows) - 1,
+                share * 100.0
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
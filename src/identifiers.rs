@@ -0,0 +1,331 @@
+use std::fmt;
+
+use anyhow::Result;
+use polars::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::report::percentage;
+use crate::streaming::collect_streaming;
+
+/// Columns with fewer non-null values than this are not judged; in a handful
+/// of rows almost every column is unique.
+const MIN_ROWS: usize = 50;
+
+/// Values looked at to tell what kind of identifier a column holds.
+const KIND_SAMPLE: usize = 100;
+
+const LEN_ALIAS: &str = "__mlcheck_len";
+/// Prefixes of the per-column aliases in the counting query.
+const ROWS_ALIAS: &str = "__mlcheck_rows_";
+const UNIQUE_ALIAS: &str = "__mlcheck_unique_";
+const MIN_ALIAS: &str = "__mlcheck_min_";
+const MAX_ALIAS: &str = "__mlcheck_max_";
+
+/// Mean length above which unique text containing spaces is taken for free
+/// text rather than a key.
+const FREE_TEXT_LENGTH: f64 = 20.0;
+
+const UUID_PATTERN: &str =
+    r"(?i)^\{?[0-9a-f]{8}-?[0-9a-f]{4}-?[0-9a-f]{4}-?[0-9a-f]{4}-?[0-9a-f]{12}\}?$";
+
+const TIMESTAMP_PATTERN: &str =
+    r"^\d{4}-\d{2}-\d{2}([T ]\d{2}:\d{2}(:\d{2}(\.\d+)?)?)?(Z|[+-]\d{2}:?\d{2})?$";
+
+/// What an identifier-like column appears to hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierKind {
+    Uuid,
+    /// Dates or timestamps stored as text
+    Timestamp,
+    /// Consecutive integers, e.g. a row number
+    Sequence,
+    FreeText,
+    /// Any other mostly unique integer or text, e.g. `user_id`
+    Key,
+}
+
+impl fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentifierKind::Uuid => write!(f, "UUID"),
+            IdentifierKind::Timestamp => write!(f, "timestamp stored as text"),
+            IdentifierKind::Sequence => write!(f, "row number"),
+            IdentifierKind::FreeText => write!(f, "free text"),
+            IdentifierKind::Key => write!(f, "key"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Identifiers {
+    pub max_unique_pct: f64,
+    /// Only the flagged columns.
+    pub columns: Vec<IdentifierColumn>,
+}
+
+/// A column with nearly as many distinct values as rows.
+#[derive(Debug, Serialize)]
+pub struct IdentifierColumn {
+    pub column: String,
+    pub kind: IdentifierKind,
+    pub unique: usize,
+    /// Non-null rows.
+    pub rows: usize,
+    /// `unique` as a percentage of `rows`.
+    pub percentage: f64,
+}
+
+impl fmt::Display for IdentifierColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "likely identifier ({}), {} distinct values in {} rows ({:.1}%); exclude it from features",
+            self.kind, self.unique, self.rows, self.percentage
+        )
+    }
+}
+
+/// Flags integer and text columns whose distinct values exceed
+/// `max_unique_pct` percent of their non-null rows. Floats and real temporal
+/// columns are skipped, since continuous features are expected to be mostly
+/// unique.
+pub fn detect(df: &DataFrame, max_unique_pct: f64, skip: Option<&str>) -> Result<Identifiers> {
+    detect_with(
+        &df.clone().lazy(),
+        df.schema(),
+        max_unique_pct,
+        skip,
+        |lf| lf.collect(),
+    )
+}
+
+/// Streaming counterpart of [`detect`]. Counting distinct values holds every
+/// distinct value of the candidate columns at once, so an ID column costs
+/// memory in proportion to the rows; the kind of a flagged text column is
+/// judged from its first values only.
+pub fn detect_lazy(
+    lf: &LazyFrame,
+    schema: &Schema,
+    max_unique_pct: f64,
+    skip: Option<&str>,
+) -> Result<Identifiers> {
+    detect_with(lf, schema, max_unique_pct, skip, collect_streaming)
+}
+
+/// Distinct and non-null counts and integer ranges of every candidate column
+/// come from one `select`; flagged text columns then read their first
+/// [`KIND_SAMPLE`] values.
+fn detect_with(
+    lf: &LazyFrame,
+    schema: &Schema,
+    max_unique_pct: f64,
+    skip: Option<&str>,
+    collect: impl Fn(LazyFrame) -> PolarsResult<DataFrame>,
+) -> Result<Identifiers> {
+    let candidates: Vec<(&PlSmallStr, &DataType)> = schema
+        .iter()
+        .filter(|(name, dtype)| {
+            Some(name.as_str()) != skip
+                && (dtype.is_integer() || dtype.is_string() || dtype.is_categorical())
+        })
+        .collect();
+    let mut columns = Vec::new();
+    if candidates.is_empty() {
+        return Ok(Identifiers {
+            max_unique_pct,
+            columns,
+        });
+    }
+
+    let mut exprs = vec![len().alias(LEN_ALIAS)];
+    for (i, (name, dtype)) in candidates.iter().enumerate() {
+        let column = col((*name).clone());
+        exprs.push(column.clone().count().alias(format!("{}{}", ROWS_ALIAS, i)));
+        exprs.push(
+            column
+                .clone()
+                .n_unique()
+                .alias(format!("{}{}", UNIQUE_ALIAS, i)),
+        );
+        if dtype.is_integer() {
+            let values = column.cast(DataType::Int64);
+            exprs.push(values.clone().min().alias(format!("{}{}", MIN_ALIAS, i)));
+            exprs.push(values.max().alias(format!("{}{}", MAX_ALIAS, i)));
+        }
+    }
+    let stats = collect(lf.clone().select(exprs))?;
+    let scalar = |alias: String| -> Result<Option<i64>> {
+        Ok(stats.column(&alias)?.cast(&DataType::Int64)?.i64()?.get(0))
+    };
+    let len = scalar(LEN_ALIAS.to_string())?.unwrap_or(0) as usize;
+
+    let uuid = Regex::new(UUID_PATTERN)?;
+    let timestamp = Regex::new(TIMESTAMP_PATTERN)?;
+    for (i, (name, dtype)) in candidates.iter().enumerate() {
+        let rows = scalar(format!("{}{}", ROWS_ALIAS, i))?.unwrap_or(0) as usize;
+        if rows < MIN_ROWS {
+            continue;
+        }
+        // `n_unique` counts null as a value of its own.
+        let with_null = scalar(format!("{}{}", UNIQUE_ALIAS, i))?.unwrap_or(0) as usize;
+        let unique = with_null - usize::from(rows < len);
+        let unique_pct = percentage(unique, rows);
+        if unique_pct <= max_unique_pct {
+            continue;
+        }
+
+        let kind = if dtype.is_integer() {
+            integer_kind(
+                scalar(format!("{}{}", MIN_ALIAS, i))?,
+                scalar(format!("{}{}", MAX_ALIAS, i))?,
+                unique,
+                rows,
+            )
+        } else {
+            let head = collect(
+                lf.clone()
+                    .select([col((*name).clone())])
+                    .drop_nulls(None)
+                    .limit(KIND_SAMPLE as IdxSize),
+            )?;
+            text_kind(
+                head.column(name.as_str())?.as_materialized_series(),
+                &uuid,
+                &timestamp,
+            )?
+        };
+        columns.push(IdentifierColumn {
+            column: name.to_string(),
+            kind,
+            unique,
+            rows,
+            percentage: unique_pct,
+        });
+    }
+
+    Ok(Identifiers {
+        max_unique_pct,
+        columns,
+    })
+}
+
+fn integer_kind(min: Option<i64>, max: Option<i64>, unique: usize, rows: usize) -> IdentifierKind {
+    let (Some(min), Some(max)) = (min, max) else {
+        return IdentifierKind::Key;
+    };
+    // `checked_add` because a column spanning all of i64 has a range of
+    // u64::MAX.
+    if unique == rows && max.abs_diff(min).checked_add(1) == Some(unique as u64) {
+        IdentifierKind::Sequence
+    } else {
+        IdentifierKind::Key
+    }
+}
+
+/// Judged from the first [`KIND_SAMPLE`] values; every one of them has to
+/// match for a UUID or timestamp.
+fn text_kind(values: &Series, uuid: &Regex, timestamp: &Regex) -> Result<IdentifierKind> {
+    let cast = values.cast(&DataType::String)?;
+    let sample: Vec<&str> = cast
+        .str()?
+        .into_iter()
+        .flatten()
+        .take(KIND_SAMPLE)
+        .collect();
+
+    if sample.iter().all(|v| uuid.is_match(v.trim())) {
+        return Ok(IdentifierKind::Uuid);
+    }
+    if sample.iter().all(|v| timestamp.is_match(v.trim())) {
+        return Ok(IdentifierKind::Timestamp);
+    }
+    let mean_length =
+        sample.iter().map(|v| v.chars().count()).sum::<usize>() as f64 / sample.len() as f64;
+    if mean_length > FREE_TEXT_LENGTH && sample.iter().any(|v| v.trim().contains(' ')) {
+        return Ok(IdentifierKind::FreeText);
+    }
+    Ok(IdentifierKind::Key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROWS: usize = 60;
+
+    fn kinds(df: &DataFrame) -> Vec<(String, IdentifierKind)> {
+        detect(df, 95.0, None)
+            .unwrap()
+            .columns
+            .into_iter()
+            .map(|c| (c.column, c.kind))
+            .collect()
+    }
+
+    fn text(name: &str, value: impl Fn(usize) -> String) -> DataFrame {
+        let values: Vec<String> = (0..ROWS).map(value).collect();
+        DataFrame::new(vec![Column::new(name.into(), values)]).unwrap()
+    }
+
+    #[test]
+    fn uuid() {
+        let df = text("uuid", |i| format!("123e4567-e89b-12d3-a456-{:012x}", i));
+        assert_eq!(kinds(&df), [("uuid".to_string(), IdentifierKind::Uuid)]);
+    }
+
+    #[test]
+    fn timestamp() {
+        let df = text("created", |i| format!("2026-01-01T00:{:02}:00Z", i));
+        assert_eq!(
+            kinds(&df),
+            [("created".to_string(), IdentifierKind::Timestamp)]
+        );
+    }
+
+    #[test]
+    fn sequence() {
+        let rows: Vec<i64> = (100..100 + ROWS as i64).collect();
+        let df = df!("row" => rows).unwrap();
+        assert_eq!(kinds(&df), [("row".to_string(), IdentifierKind::Sequence)]);
+    }
+
+    #[test]
+    fn free_text() {
+        let df = text("comment", |i| {
+            format!("customer number {} wrote a review", i)
+        });
+        assert_eq!(
+            kinds(&df),
+            [("comment".to_string(), IdentifierKind::FreeText)]
+        );
+    }
+
+    #[test]
+    fn integers_spanning_all_of_i64_are_a_key() {
+        let mut values: Vec<i64> = (0..ROWS as i64 - 2).collect();
+        values.extend([i64::MIN, i64::MAX]);
+        let df = df!("id" => values).unwrap();
+        assert_eq!(kinds(&df), [("id".to_string(), IdentifierKind::Key)]);
+    }
+
+    #[test]
+    fn streaming_ignores_nulls_when_counting() {
+        let values: Vec<Option<i64>> = (0..ROWS as i64)
+            .map(|i| (i % 10 != 0).then_some(i))
+            .collect();
+        let df = df!("id" => values).unwrap();
+        let found = detect_lazy(&df.clone().lazy(), df.schema(), 95.0, None).unwrap();
+        let column = &found.columns[0];
+        assert_eq!((column.unique, column.rows), (54, 54));
+        assert_eq!(column.kind, IdentifierKind::Key);
+    }
+
+    #[test]
+    fn repeated_values_are_not_flagged() {
+        let values: Vec<i64> = (0..ROWS as i64).map(|i| i % 3).collect();
+        let df = df!("bucket" => values).unwrap();
+        assert!(kinds(&df).is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::check::CheckKind;
+use crate::html::escape;
+use crate::report::{Finding, ValidationReport};
+
+/// One `<testcase>`: a check applied to one column, or to the whole file when
+/// `column` is `None`.
+struct Case<'a> {
+    check: String,
+    column: Option<String>,
+    findings: Vec<&'a Finding>,
+    skipped: Option<&'static str>,
+}
+
+impl<'a> Case<'a> {
+    fn new(check: &str, column: Option<String>) -> Self {
+        Case {
+            check: check.to_string(),
+            column,
+            findings: Vec::new(),
+            skipped: None,
+        }
+    }
+
+    /// A case for a check that did not run at all.
+    fn skipped(report: &ValidationReport, check: CheckKind) -> Self {
+        let mut case = Case::new(&check.to_string(), None);
+        case.skipped = Some(report.skip_reason(check));
+        case
+    }
+}
+
+/// Renders every check the validation ran as a JUnit test case. A case fails
+/// when it raised a finding at or above `--fail-on`; lower findings are kept
+/// in its `<system-out>`.
+pub fn validation_junit(report: &ValidationReport) -> String {
+    let columns = &report.columns;
+    let mut cases = vec![Case::new("schema", None)];
+
+    match &report.missing {
+        Some(_) => cases.extend(
+            columns
+                .iter()
+                .map(|c| Case::new("missing", Some(c.clone()))),
+        ),
+        None => cases.push(Case::skipped(report, CheckKind::Missing)),
+    }
+    match &report.duplicates {
+        Some(_) => cases.push(Case::new("duplicates", None)),
+        None => cases.push(Case::skipped(report, CheckKind::Duplicates)),
+    }
+    match &report.outliers {
+        Some(outliers) => cases.extend(
+            outliers
+                .columns
+                .iter()
+                .map(|c| Case::new("outliers", Some(c.column.clone()))),
+        ),
+        None => cases.push(Case::skipped(report, CheckKind::Outliers)),
+    }
+    match &report.constant {
+        Some(_) => cases.extend(
+            columns
+                .iter()
+                .map(|c| Case::new("constant", Some(c.clone()))),
+        ),
+        None => cases.push(Case::skipped(report, CheckKind::Constant)),
+    }
+    match &report.identifiers {
+        Some(_) => cases.extend(
+            columns
+                .iter()
+                .map(|c| Case::new("identifiers", Some(c.clone()))),
+        ),
+        None => cases.push(Case::skipped(report, CheckKind::Identifiers)),
+    }
+    if let Some(target) = &report.target {
+        cases.push(Case::new("target", Some(target.column.clone())));
+        if target.leakage.is_some() {
+            cases.extend(
+                columns
+                    .iter()
+                    .filter(|c| **c != target.column)
+                    .map(|c| Case::new("leakage", Some(c.clone()))),
+            );
+        }
+    }
+    if report.contract.is_some() {
+        cases.extend(
+            columns
+                .iter()
+                .map(|c| Case::new("contract", Some(c.clone()))),
+        );
+    }
+
+    for finding in &report.findings {
+        let position = cases
+            .iter()
+            .position(|c| c.check == finding.check && c.column == finding.column);
+        match position {
+            Some(i) => cases[i].findings.push(finding),
+            None => {
+                // Columns only known from the finding, e.g. one the contract
+                // requires but the file lacks.
+                let mut case = Case::new(&finding.check, finding.column.clone());
+                case.findings.push(finding);
+                cases.push(case);
+            }
+        }
+    }
+
+    let failed = |case: &Case| case.findings.iter().any(|f| f.severity >= report.fail_on);
+    let failures = cases.iter().filter(|c| failed(c)).count();
+    let skipped = cases.iter().filter(|c| c.skipped.is_some()).count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"mlcheck\" tests=\"{0}\" failures=\"{1}\" errors=\"0\" skipped=\"{2}\">\n  <testsuite name=\"{3}\" tests=\"{0}\" failures=\"{1}\" errors=\"0\" skipped=\"{2}\">\n",
+        cases.len(),
+        failures,
+        skipped,
+        escape(&report.file)
+    ));
+
+    for case in &cases {
+        xml.push_str(&format!(
+            "    <testcase classname=\"mlcheck.{}\" name=\"{}\" time=\"0\"",
+            case.check,
+            escape(case.column.as_deref().unwrap_or(&case.check))
+        ));
+        if case.findings.is_empty() && case.skipped.is_none() {
+            xml.push_str("/>\n");
+            continue;
+        }
+        xml.push_str(">\n");
+
+        if let Some(reason) = case.skipped {
+            xml.push_str(&format!(
+                "      <skipped message=\"{}\"/>\n",
+                escape(reason)
+            ));
+        }
+        let (failing, other): (Vec<&Finding>, Vec<&Finding>) = case
+            .findings
+            .iter()
+            .partition(|f| f.severity >= report.fail_on);
+        if let Some(first) = failing.first() {
+            xml.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                first.severity,
+                escape(&first.message),
+                escape(&lines(&failing))
+            ));
+        }
+        if !other.is_empty() {
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape(&lines(&other))
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>");
+    xml
+}
+
+fn lines(findings: &[&Finding]) -> String {
+    findings
+        .iter()
+        .map(|f| format!("[{}] {}", f.severity, f.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
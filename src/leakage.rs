@@ -0,0 +1,558 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use polars::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::report::print_load_warnings;
+use crate::stats;
+use crate::target::TargetTask;
+
+/// How many leaked keys are kept as examples.
+const MAX_EXAMPLES: usize = 5;
+
+const ROW_ALIAS: &str = "__mlcheck_row";
+
+/// Below this many labelled rows a perfect fit is too likely by chance for
+/// the statistical target-leakage heuristics to mean anything.
+const MIN_LEAKAGE_ROWS: usize = 30;
+
+/// Absolute Pearson correlation with a numeric target treated as leakage.
+const MAX_CORRELATION: f64 = 0.95;
+
+/// Share of the target's entropy explained by one feature treated as leakage.
+const MAX_INFORMATION_RATIO: f64 = 0.9;
+
+/// Quantile bins used to discretize numeric columns for mutual information.
+const INFORMATION_BINS: usize = 10;
+
+/// Features need this many rows per category on average before mutual
+/// information is computed; ID-like columns explain any target trivially.
+const MIN_ROWS_PER_CATEGORY: usize = 10;
+
+/// Words in a column name that usually mean it holds the label itself or
+/// something computed from it.
+const LEAKY_NAMES: &str = "target|label|outcome|y_true|ground_truth|pred|prediction|predicted";
+
+/// Words that, joined to the target's name as in `y_pred` or `true_price`,
+/// mark a column as a copy or estimate of the target.
+const DERIVED_WORDS: &str =
+    "pred|prediction|predicted|true|hat|est|estimate|score|proba|prob|encoded|enc";
+
+#[derive(Debug, Serialize)]
+pub struct LeakageReport {
+    pub schema_version: u32,
+    pub train: String,
+    pub test: String,
+    pub warnings: Vec<String>,
+    /// Columns whose values must all match for a test row to count as leaked.
+    pub keys: Vec<String>,
+    /// Shared list or struct columns left out of the default keys, since
+    /// nested values cannot be compared.
+    pub nested_columns: Vec<String>,
+    pub test_rows: usize,
+    /// Test rows whose key also occurs in the train file.
+    pub leaked_rows: usize,
+    pub percentage: f64,
+    /// Distinct keys shared by both files.
+    pub leaked_keys: usize,
+    pub examples: Vec<LeakedRow>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeakedRow {
+    /// Zero-based row index in the test file.
+    pub row: usize,
+    pub key: Vec<KeyValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyValue {
+    pub column: String,
+    pub value: Option<String>,
+}
+
+pub struct Overlap {
+    pub keys: Vec<String>,
+    pub nested_columns: Vec<String>,
+    pub leaked_rows: usize,
+    pub leaked_keys: usize,
+    pub examples: Vec<LeakedRow>,
+}
+
+/// Finds test rows whose `keys` columns match a train row exactly. Without
+/// explicit keys every column shared by both files is compared, which catches
+/// whole rows copied across splits; list and struct columns are left out of
+/// those and rejected as explicit keys. Nulls compare equal, and each key is
+/// cast to a type both sides share (see [`key_type`]), so an `i64` id still
+/// matches the same id read as `f64`.
+pub fn find_overlap(train: &DataFrame, test: &DataFrame, keys: &[String]) -> Result<Overlap> {
+    let mut nested_columns = Vec::new();
+    let keys: Vec<String> = if keys.is_empty() {
+        let mut keys = Vec::new();
+        for column in test.get_columns() {
+            let Ok(other) = train.column(column.name()) else {
+                continue;
+            };
+            if column.dtype().is_nested() || other.dtype().is_nested() {
+                nested_columns.push(column.name().to_string());
+            } else {
+                keys.push(column.name().to_string());
+            }
+        }
+        keys
+    } else {
+        for key in keys {
+            for (df, side) in [(train, "train"), (test, "test")] {
+                let Ok(column) = df.column(key) else {
+                    bail!("key column '{}' not found in the {} file", key, side);
+                };
+                if column.dtype().is_nested() {
+                    bail!(
+                        "key column '{}' is {} in the {} file; list and struct columns cannot be compared",
+                        key,
+                        column.dtype(),
+                        side
+                    );
+                }
+            }
+        }
+        keys.to_vec()
+    };
+    if keys.is_empty() {
+        if nested_columns.is_empty() {
+            bail!("train and test files share no columns to compare");
+        }
+        bail!(
+            "train and test files share only list or struct columns ({}), which cannot be compared",
+            nested_columns.join(", ")
+        );
+    }
+
+    let key_types = keys
+        .iter()
+        .map(|key| {
+            Ok(key_type(
+                train.column(key)?.dtype(),
+                test.column(key)?.dtype(),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let cast_keys = || -> Vec<Expr> {
+        keys.iter()
+            .zip(&key_types)
+            .map(|(key, dtype)| col(key.as_str()).strict_cast(dtype.clone()))
+            .collect()
+    };
+    let on: Vec<Expr> = keys.iter().map(|key| col(key.as_str())).collect();
+
+    let train_keys = train
+        .clone()
+        .lazy()
+        .select(cast_keys())
+        .unique(None, UniqueKeepStrategy::Any);
+    let test_keys = test
+        .clone()
+        .lazy()
+        .select(cast_keys())
+        .with_row_index(ROW_ALIAS, None);
+
+    let mut args = JoinArgs::new(JoinType::Inner);
+    args.nulls_equal = true;
+    args.maintain_order = MaintainOrderJoin::Left;
+    let leaked = test_keys.join(train_keys, &on, &on, args).collect()?;
+
+    let leaked_keys = leaked
+        .clone()
+        .lazy()
+        .select(on.clone())
+        .unique(None, UniqueKeepStrategy::Any)
+        .collect()?
+        .height();
+
+    let head = leaked.head(Some(MAX_EXAMPLES));
+    let rows = head.column(ROW_ALIAS)?.cast(&DataType::UInt64)?;
+    let mut examples = Vec::new();
+    for (i, row) in rows.u64()?.into_no_null_iter().enumerate() {
+        let key = keys
+            .iter()
+            .map(|key| {
+                let values = head.column(key)?.cast(&DataType::String)?;
+                let value = values.str()?.get(i).map(str::to_string);
+                Ok(KeyValue {
+                    column: key.clone(),
+                    value,
+                })
+            })
+            .collect::<Result<_>>()?;
+        examples.push(LeakedRow {
+            row: row as usize,
+            key,
+        });
+    }
+
+    Ok(Overlap {
+        keys,
+        nested_columns,
+        leaked_rows: leaked.height(),
+        leaked_keys,
+        examples,
+    })
+}
+
+/// Type a key column is compared as: integers as `i64`, other numbers as
+/// `f64` and anything else, including a number on one side only, as text.
+fn key_type(train: &DataType, test: &DataType) -> DataType {
+    if train.is_integer() && test.is_integer() {
+        DataType::Int64
+    } else if train.is_primitive_numeric() && test.is_primitive_numeric() {
+        DataType::Float64
+    } else {
+        DataType::String
+    }
+}
+
+/// Formats of the leakage report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LeakageFormat {
+    Text,
+    Json,
+}
+
+impl LeakageReport {
+    pub fn print(&self, format: LeakageFormat) -> Result<()> {
+        match format {
+            LeakageFormat::Text => self.print_text(),
+            LeakageFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+        Ok(())
+    }
+
+    fn print_text(&self) {
+        println!("🕳️  Leakage: {} → {}\n", self.train, self.test);
+        print_load_warnings(&self.warnings);
+
+        println!("🔑 Keys: {}", self.keys.join(", "));
+        if !self.nested_columns.is_empty() {
+            println!(
+                "├─ Not compared (list or struct values): {}",
+                self.nested_columns.join(", ")
+            );
+        }
+        if self.leaked_rows == 0 {
+            println!("└─ ✓ No test rows appear in the train file");
+            return;
+        }
+
+        println!(
+            "├─ ❌ {} of {} test rows ({:.1}%) also appear in train",
+            self.leaked_rows, self.test_rows, self.percentage
+        );
+        println!("├─ {} distinct shared keys", self.leaked_keys);
+        println!("└─ Examples:");
+        for (i, example) in self.examples.iter().enumerate() {
+            let branch = if i + 1 == self.examples.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            let key = example
+                .key
+                .iter()
+                .map(|k| format!("{}={}", k.column, k.value.as_deref().unwrap_or("null")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("   {} row {}: {}", branch, example.row, key);
+        }
+    }
+}
+
+/// A feature column that looks like it gives the target away.
+#[derive(Debug, Serialize)]
+pub struct LeakageSuspect {
+    pub column: String,
+    /// `name`, `correlation`, `separation` or `mutual_information`.
+    pub rule: String,
+    pub score: Option<f64>,
+    pub message: String,
+}
+
+/// Flags features that predict the target suspiciously well. The name rule
+/// always runs; the statistical rules need [`MIN_LEAKAGE_ROWS`] labelled rows
+/// and report at most one hit per column, strongest evidence first.
+pub fn target_leakage(
+    df: &DataFrame,
+    target: &Column,
+    task: TargetTask,
+) -> Result<Vec<LeakageSuspect>> {
+    // Whole words only, so `predator` is not taken for `pred`.
+    let leaky_word = Regex::new(&format!(r"(?i)(^|[_\W])({})($|[_\W])", LEAKY_NAMES))?;
+    // The target's name must make up the whole column name, bar a derived
+    // word, so a target `y` flags `y_pred` but not `pos_y`.
+    let derived_name = Regex::new(&format!(
+        r"(?i)^(({words})[_\W]*)?{target}([_\W]*({words}))?$",
+        words = DERIVED_WORDS,
+        target = regex::escape(target.name()),
+    ))?;
+
+    let target_values = numeric_values(target)?;
+    let target_codes = match (task, &target_values) {
+        (TargetTask::Regression, Some(values)) => quantile_codes(values, INFORMATION_BINS),
+        _ => category_codes(target)?,
+    };
+    let labelled = target_codes.iter().flatten().count();
+
+    let mut suspects = Vec::new();
+    for col in df.get_columns() {
+        if col.name() == target.name() {
+            continue;
+        }
+        let suspect = |rule: &str, score: Option<f64>, message: String| LeakageSuspect {
+            column: col.name().to_string(),
+            rule: rule.to_string(),
+            score,
+            message,
+        };
+
+        if leaky_word.is_match(col.name()) || derived_name.is_match(col.name()) {
+            suspects.push(suspect(
+                "name",
+                None,
+                "name suggests it is derived from the target".to_string(),
+            ));
+        }
+        if labelled < MIN_LEAKAGE_ROWS {
+            continue;
+        }
+
+        let values = numeric_values(col)?;
+        if let (Some(x), Some(y)) = (&values, &target_values) {
+            let (x, y): (Vec<f64>, Vec<f64>) = x
+                .iter()
+                .zip(y)
+                .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+                .unzip();
+            if let Some(r) = stats::pearson(&x, &y).filter(|r| r.abs() >= MAX_CORRELATION) {
+                suspects.push(suspect(
+                    "correlation",
+                    Some(r),
+                    format!("correlation {:.3} with the target", r),
+                ));
+                continue;
+            }
+        }
+
+        if task == TargetTask::Classification
+            && let Some(x) = &values
+            && separates_classes(x, &target_codes)
+        {
+            suspects.push(suspect(
+                "separation",
+                Some(1.0),
+                "a single threshold perfectly separates the target classes".to_string(),
+            ));
+            continue;
+        }
+
+        let codes = match &values {
+            Some(x) => quantile_codes(x, INFORMATION_BINS),
+            None => category_codes(col)?,
+        };
+        let (a, b): (Vec<usize>, Vec<usize>) = codes
+            .iter()
+            .zip(&target_codes)
+            .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+            .unzip();
+        let categories = a.iter().collect::<HashSet<_>>().len();
+        let target_entropy = stats::entropy(&b);
+        if categories * MIN_ROWS_PER_CATEGORY > a.len() || target_entropy == 0.0 {
+            continue;
+        }
+        let ratio = stats::mutual_information(&a, &b) / target_entropy;
+        if ratio >= MAX_INFORMATION_RATIO {
+            suspects.push(suspect(
+                "mutual_information",
+                Some(ratio),
+                format!(
+                    "explains {:.0}% of the target's entropy (mutual information)",
+                    ratio * 100.0
+                ),
+            ));
+        }
+    }
+
+    Ok(suspects)
+}
+
+/// Values of a numeric column with NaN mapped to null, or `None` for other
+/// dtypes.
+fn numeric_values(col: &Column) -> Result<Option<Vec<Option<f64>>>> {
+    if !col.dtype().is_primitive_numeric() {
+        return Ok(None);
+    }
+    let cast = col.cast(&DataType::Float64)?;
+    Ok(Some(
+        cast.f64()?
+            .into_iter()
+            .map(|v| v.filter(|v| !v.is_nan()))
+            .collect(),
+    ))
+}
+
+fn category_codes(col: &Column) -> Result<Vec<Option<usize>>> {
+    let cast = col.cast(&DataType::String)?;
+    let mut codes: HashMap<&str, usize> = HashMap::new();
+    Ok(cast
+        .str()?
+        .into_iter()
+        .map(|v| {
+            let next = codes.len();
+            v.map(|v| *codes.entry(v).or_insert(next))
+        })
+        .collect())
+}
+
+fn quantile_codes(values: &[Option<f64>], bins: usize) -> Vec<Option<usize>> {
+    let mut sorted: Vec<f64> = values.iter().flatten().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    let mut edges: Vec<f64> = (1..bins)
+        .filter_map(|i| stats::quantile(&sorted, i as f64 / bins as f64))
+        .collect();
+    edges.dedup();
+    values
+        .iter()
+        .map(|v| v.map(|v| edges.partition_point(|&edge| edge < v)))
+        .collect()
+}
+
+/// Whether sorting by `values` puts every class in one contiguous run, i.e.
+/// thresholds on this column alone classify every row correctly.
+fn separates_classes(values: &[Option<f64>], classes: &[Option<usize>]) -> bool {
+    let mut pairs: Vec<(f64, usize)> = values
+        .iter()
+        .zip(classes)
+        .filter_map(|(v, c)| Some(((*v)?, (*c)?)))
+        .collect();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let distinct = pairs.iter().map(|p| p.1).collect::<HashSet<_>>().len();
+    if distinct < 2 {
+        return false;
+    }
+
+    let mut runs = 1;
+    for window in pairs.windows(2) {
+        let ((v1, c1), (v2, c2)) = (window[0], window[1]);
+        if c1 != c2 {
+            if v1 == v2 {
+                return false;
+            }
+            runs += 1;
+        }
+    }
+    runs == distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_key_matches_same_id_read_as_float() {
+        let train = df!("id" => [1i64, 2, 3]).unwrap();
+        let test = df!("id" => [1.0f64, 5.0, 3.0]).unwrap();
+        let overlap = find_overlap(&train, &test, &[]).unwrap();
+        assert_eq!(overlap.leaked_rows, 2);
+        assert_eq!(overlap.leaked_keys, 2);
+        let rows: Vec<usize> = overlap.examples.iter().map(|e| e.row).collect();
+        assert_eq!(rows, [0, 2]);
+    }
+
+    #[test]
+    fn number_and_text_keys_compare_as_text() {
+        let train = df!("id" => [1i64, 2]).unwrap();
+        let test = df!("id" => ["1", "1.0"]).unwrap();
+        let overlap = find_overlap(&train, &test, &["id".to_string()]).unwrap();
+        assert_eq!(overlap.leaked_rows, 1);
+        assert_eq!(overlap.examples[0].key[0].value.as_deref(), Some("1"));
+    }
+
+    fn suspects(df: &DataFrame, target: &str, task: TargetTask) -> Vec<(String, String)> {
+        target_leakage(df, df.column(target).unwrap(), task)
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.column, s.rule))
+            .collect()
+    }
+
+    #[test]
+    fn name_rule_needs_the_whole_target_name() {
+        let df = df!(
+            "y" => [1i64, 0, 1],
+            "pos_y" => [1i64, 2, 3],
+            "y_pred" => [1i64, 2, 3],
+            "yhat" => [1i64, 2, 3],
+            "true_y" => [1i64, 2, 3],
+            "ground_truth" => [1i64, 2, 3],
+            "predator_count" => [1i64, 2, 3],
+        )
+        .unwrap();
+        let names: Vec<String> = suspects(&df, "y", TargetTask::Classification)
+            .into_iter()
+            .map(|(column, _)| column)
+            .collect();
+        assert_eq!(names, ["y_pred", "yhat", "true_y", "ground_truth"]);
+    }
+
+    #[test]
+    fn feature_tracking_the_target_is_flagged_by_correlation() {
+        let price: Vec<f64> = (0..40).map(|i| i as f64 * 10.0).collect();
+        let noise: Vec<f64> = (0..40).map(|i| ((i * 7) % 11) as f64).collect();
+        let df = df!(
+            "price" => &price,
+            "price_in_cents" => price.iter().map(|p| p * 100.0).collect::<Vec<_>>(),
+            "rooms" => noise,
+        )
+        .unwrap();
+        assert_eq!(
+            suspects(&df, "price", TargetTask::Regression),
+            [("price_in_cents".to_string(), "correlation".to_string())]
+        );
+    }
+
+    #[test]
+    fn nested_columns_are_left_out_of_default_keys() {
+        let tags = |values: [&str; 2]| {
+            Series::new(
+                "tags".into(),
+                values.map(|value| Series::new("".into(), [value])),
+            )
+        };
+        let train = DataFrame::new(vec![
+            Series::new("id".into(), [1i64, 2]).into(),
+            tags(["a", "b"]).into(),
+        ])
+        .unwrap();
+        let test = DataFrame::new(vec![
+            Series::new("id".into(), [2i64, 3]).into(),
+            tags(["c", "d"]).into(),
+        ])
+        .unwrap();
+
+        let overlap = find_overlap(&train, &test, &[]).unwrap();
+        assert_eq!(overlap.keys, ["id"]);
+        assert_eq!(overlap.nested_columns, ["tags"]);
+        assert_eq!(overlap.leaked_rows, 1);
+
+        let error = find_overlap(&train, &test, &["tags".to_string()])
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .contains("list and struct columns cannot be compared")
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Dataset checks behind the `mlcheck` CLI.
+//!
+//! Every check implements [`Check`]: it takes a polars `DataFrame` and returns
+//! [`Finding`]s with a [`Severity`]. [`validate::validate_frame`] and
+//! [`validate::validate_path`] run the same set of checks as `mlcheck
+//! validate` and return the full [`ValidationReport`], which can be rendered
+//! as text, JSON, HTML, Markdown or JUnit XML.
+
+pub mod budget;
+pub mod check;
+pub mod compression;
+pub mod config;
+pub mod contract;
+pub mod dialect;
+pub mod drift;
+pub mod html;
+pub mod identifiers;
+pub mod junit;
+pub mod leakage;
+pub mod loader;
+pub mod markdown;
+pub mod outliers;
+pub mod profile;
+pub mod report;
+pub mod sample;
+pub mod shards;
+pub mod stats;
+pub mod streaming;
+pub mod target;
+pub mod validate;
+
+pub use check::Check;
+pub use report::{Finding, Severity, ValidationReport};
+pub use validate::{ValidateOptions, validate_frame, validate_path};
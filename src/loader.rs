@@ -0,0 +1,572 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use polars::io::mmap::MmapBytesReader;
+use polars::prelude::*;
+use serde_json::Value;
+
+use crate::compression::{self, Compression, Spill};
+use crate::dialect::{CsvOptions, Encoding, read_csv, read_csv_bytes, scan_csv};
+use crate::sample::{self, Sample, SampleSize};
+use crate::shards;
+
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// Path argument that reads the dataset from standard input.
+pub const STDIN: &str = "-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Csv,
+    Parquet,
+    #[value(name = "ndjson", alias = "jsonl")]
+    NdJson,
+}
+
+impl Format {
+    fn from_extension(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" | "tsv" | "txt" => Some(Format::Csv),
+            "parquet" | "pq" => Some(Format::Parquet),
+            "jsonl" | "ndjson" => Some(Format::NdJson),
+            _ => None,
+        }
+    }
+
+    fn from_magic(head: &[u8]) -> Option<Self> {
+        if head.starts_with(PARQUET_MAGIC) {
+            return Some(Format::Parquet);
+        }
+        if head.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{') {
+            return Some(Format::NdJson);
+        }
+        None
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Csv => write!(f, "CSV"),
+            Format::Parquet => write!(f, "Parquet"),
+            Format::NdJson => write!(f, "NDJSON"),
+        }
+    }
+}
+
+/// A loaded dataset together with what was learned while reading it.
+pub struct Dataset {
+    pub df: DataFrame,
+    pub format: Format,
+    /// Non-fatal issues found while loading, e.g. schema conflicts between
+    /// NDJSON lines or between the shards of a partitioned dataset.
+    pub warnings: Vec<String>,
+    /// Number of files read; more than one for a directory or glob.
+    pub files: usize,
+    pub compression: Option<Compression>,
+}
+
+/// Detects the file format from the extension, falling back to magic bytes
+/// and finally to CSV. For compressed files the extension before `.gz` etc.
+/// and the magic bytes of the decompressed data are used.
+pub fn detect_format(path: &str) -> Result<Format> {
+    if let Some(format) = Format::from_extension(&compression::strip_extension(Path::new(path))) {
+        return Ok(format);
+    }
+
+    let reader: Box<dyn Read> = match Compression::detect(path)? {
+        Some(compression) => compression.open(path)?,
+        None => Box::new(open(path)?),
+    };
+    let mut head = Vec::new();
+    reader
+        .take(PARQUET_MAGIC.len() as u64)
+        .read_to_end(&mut head)?;
+    Ok(Format::from_magic(&head).unwrap_or(Format::Csv))
+}
+
+/// Reads a file, or every data file of a directory or glob stacked into one
+/// frame with Hive partition columns (`date=2026-01-01/`) added. [`STDIN`]
+/// reads from standard input. `format` overrides format detection.
+pub fn read_dataset(path: &str, format: Option<Format>, csv: &CsvOptions) -> Result<Dataset> {
+    if path == STDIN {
+        return read_stdin(format, csv);
+    }
+    let Some(shards) = shards::discover(path)? else {
+        return read_file(path, format, csv);
+    };
+
+    let mut detected = None;
+    let mut compression = None;
+    let mut warnings = Vec::new();
+    let mut frames = Vec::new();
+    let mut schemas = Vec::new();
+    for shard in &shards.files {
+        let dataset = read_file(&shard.path, format, csv)?;
+        check_shard_format(&mut detected, dataset.format, &shard.path)?;
+        compression = compression.or(dataset.compression);
+        warnings.extend(
+            dataset
+                .warnings
+                .into_iter()
+                .map(|w| format!("{}: {}", shard.path, w)),
+        );
+        schemas.push(dataset.df.schema().as_ref().clone());
+        frames.push(dataset.df.lazy());
+    }
+    warnings.extend(shards.schema_mismatches(&schemas));
+
+    Ok(Dataset {
+        df: shards.combine(frames, &schemas)?.collect()?,
+        format: detected.unwrap_or(Format::Csv),
+        warnings,
+        files: shards.files.len(),
+        compression,
+    })
+}
+
+/// Reads a random sample of a dataset, the same rows [`sample::sample`] would
+/// draw from the full frame. Files are scanned so only the sampled rows are
+/// materialized; stdin cannot be scanned and is read whole before sampling.
+pub fn read_dataset_sample(
+    path: &str,
+    format: Option<Format>,
+    csv: &CsvOptions,
+    size: SampleSize,
+    seed: u64,
+) -> Result<(Dataset, Sample)> {
+    if !scannable(path, csv)? {
+        let mut dataset = read_dataset(path, format, csv)?;
+        let (df, sample) = sample::sample(&dataset.df, size, seed)?;
+        dataset.df = df;
+        return Ok((dataset, sample));
+    }
+
+    let scan = scan_dataset(path, format, csv, None)?;
+    let (df, sample) = sample::sample_lazy(scan.lf, size, seed)?;
+    let dataset = Dataset {
+        df,
+        format: scan.format,
+        warnings: scan.warnings,
+        files: scan.files,
+        compression: scan.compression,
+    };
+    Ok((dataset, sample))
+}
+
+/// Whether `path` can be sampled from a lazy scan. Stdin, Latin-1 text and
+/// compressed files (which a scan would decompress to disk) are read whole
+/// instead.
+fn scannable(path: &str, csv: &CsvOptions) -> Result<bool> {
+    if path == STDIN || csv.encoding == Encoding::Latin1 {
+        return Ok(false);
+    }
+    let paths = match shards::discover(path)? {
+        Some(shards) => shards.files.into_iter().map(|shard| shard.path).collect(),
+        None => vec![path.to_string()],
+    };
+    for path in &paths {
+        if Compression::detect(path)?.is_some() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn check_shard_format(format: &mut Option<Format>, found: Format, path: &str) -> Result<()> {
+    match format {
+        Some(expected) if *expected != found => {
+            bail!("{} is {}, but earlier files are {}", path, found, expected)
+        }
+        _ => *format = Some(found),
+    }
+    Ok(())
+}
+
+/// Compressed files are decompressed into memory as they are read, never to
+/// disk.
+fn read_file(path: &str, format: Option<Format>, csv: &CsvOptions) -> Result<Dataset> {
+    let format = match format {
+        Some(format) => format,
+        None => detect_format(path)?,
+    };
+    let compression = Compression::detect(path)?;
+    let mut warnings = Vec::new();
+
+    let df = match compression {
+        None => match format {
+            Format::Csv => read_csv(path, csv)?,
+            Format::Parquet => read_parquet(path)?,
+            Format::NdJson => {
+                warnings = ndjson_schema_conflicts(BufReader::new(open(path)?))?;
+                read_ndjson(open(path)?, !warnings.is_empty())?
+            }
+        },
+        Some(compression) => {
+            read_bytes(compression.read_to_end(path)?, format, csv, &mut warnings)?
+        }
+    };
+
+    Ok(Dataset {
+        df,
+        format,
+        warnings,
+        files: 1,
+        compression,
+    })
+}
+
+/// Buffers all of stdin, since none of the readers can parse a pipe
+/// incrementally. Compression and the format are detected from the data
+/// unless `format` is given.
+fn read_stdin(format: Option<Format>, csv: &CsvOptions) -> Result<Dataset> {
+    let mut bytes = Vec::new();
+    io::stdin()
+        .lock()
+        .read_to_end(&mut bytes)
+        .context("failed to read stdin")?;
+    if bytes.is_empty() {
+        bail!("no data on stdin");
+    }
+
+    let compression = Compression::from_magic(&bytes);
+    if let Some(compression) = compression {
+        bytes = compression.decompress(&bytes)?;
+    }
+    let format = format
+        .or_else(|| Format::from_magic(&bytes))
+        .unwrap_or(Format::Csv);
+    let mut warnings = Vec::new();
+    let df = read_bytes(bytes, format, csv, &mut warnings)?;
+
+    Ok(Dataset {
+        df,
+        format,
+        warnings,
+        files: 1,
+        compression,
+    })
+}
+
+/// Parses a whole (decompressed) file held in memory.
+fn read_bytes(
+    bytes: Vec<u8>,
+    format: Format,
+    csv: &CsvOptions,
+    warnings: &mut Vec<String>,
+) -> Result<DataFrame> {
+    match format {
+        Format::Csv => read_csv_bytes(bytes, csv),
+        Format::Parquet => Ok(ParquetReader::new(Cursor::new(bytes)).finish()?),
+        Format::NdJson => {
+            *warnings = ndjson_schema_conflicts(&bytes[..])?;
+            read_ndjson(Cursor::new(bytes), !warnings.is_empty())
+        }
+    }
+}
+
+fn open(path: &str) -> Result<File> {
+    File::open(path).with_context(|| format!("failed to open {}", path))
+}
+
+/// A lazily scanned dataset, the streaming counterpart of [`Dataset`].
+pub struct Scan {
+    pub lf: LazyFrame,
+    pub format: Format,
+    pub warnings: Vec<String>,
+    pub files: usize,
+    pub compression: Option<Compression>,
+    /// Decompressed copies of compressed files, which `lf` reads; they are
+    /// deleted when the scan is dropped.
+    pub spills: Vec<Spill>,
+}
+
+/// Lazily scans a dataset so checks can run on the streaming engine without
+/// materializing the whole file. Compressed files are decompressed into
+/// `spill_dir`, and rejected without one.
+pub fn scan_dataset(
+    path: &str,
+    format: Option<Format>,
+    csv: &CsvOptions,
+    spill_dir: Option<&Path>,
+) -> Result<Scan> {
+    if path == STDIN {
+        bail!(
+            "stdin cannot be scanned in streaming mode; without --streaming it is read into memory"
+        );
+    }
+    let Some(shards) = shards::discover(path)? else {
+        return scan_file(path, format, csv, spill_dir);
+    };
+
+    let mut detected = None;
+    let mut compression = None;
+    let mut warnings = Vec::new();
+    let mut spills = Vec::new();
+    let mut frames = Vec::new();
+    let mut schemas = Vec::new();
+    for shard in &shards.files {
+        let mut scan = scan_file(&shard.path, format, csv, spill_dir)?;
+        check_shard_format(&mut detected, scan.format, &shard.path)?;
+        compression = compression.or(scan.compression);
+        warnings.extend(
+            scan.warnings
+                .into_iter()
+                .map(|w| format!("{}: {}", shard.path, w)),
+        );
+        spills.append(&mut scan.spills);
+        schemas.push(scan.lf.collect_schema()?.as_ref().clone());
+        frames.push(scan.lf);
+    }
+    warnings.extend(shards.schema_mismatches(&schemas));
+
+    Ok(Scan {
+        lf: shards.combine(frames, &schemas)?,
+        format: detected.unwrap_or(Format::Csv),
+        warnings,
+        files: shards.files.len(),
+        compression,
+        spills,
+    })
+}
+
+/// Total size on disk of a file or of every file in a directory or glob.
+pub fn input_size(path: &str) -> Result<u64> {
+    match shards::discover(path)? {
+        Some(shards) => shards
+            .files
+            .iter()
+            .map(|shard| Ok(std::fs::metadata(&shard.path)?.len()))
+            .sum(),
+        None => Ok(std::fs::metadata(path)?.len()),
+    }
+}
+
+/// Compressed files are first decompressed to a file in `spill_dir`, since
+/// polars can only scan plain files lazily.
+fn scan_file(
+    path: &str,
+    format: Option<Format>,
+    csv: &CsvOptions,
+    spill_dir: Option<&Path>,
+) -> Result<Scan> {
+    let format = match format {
+        Some(format) => format,
+        None => detect_format(path)?,
+    };
+    let compression = Compression::detect(path)?;
+    let spill = match (compression, spill_dir) {
+        (Some(compression), Some(dir)) => Some(compression.spill(path, dir)?),
+        (Some(compression), None) => bail!(
+            "{} is {}-compressed and can only be scanned from a decompressed copy on disk",
+            path,
+            compression
+        ),
+        (None, _) => None,
+    };
+    let path = spill.as_ref().map_or(path, Spill::path);
+    let mut warnings = Vec::new();
+
+    let lf = match format {
+        Format::Csv => scan_csv(path, csv)?,
+        Format::Parquet => LazyFrame::scan_parquet(PlPath::new(path), Default::default())?,
+        Format::NdJson => {
+            warnings = ndjson_schema_conflicts(BufReader::new(open(path)?))?;
+            let mut reader = LazyJsonLineReader::new(PlPath::new(path));
+            if !warnings.is_empty() {
+                reader = reader.with_infer_schema_length(None);
+            }
+            let mut lf = reader.finish()?;
+            let schema = lf.collect_schema()?;
+            let mut exprs = Vec::with_capacity(schema.len());
+            for (name, dtype) in schema.iter() {
+                flatten_struct_expr(col(name.clone()), name, dtype, &mut exprs);
+            }
+            lf.select(exprs)
+        }
+    };
+
+    Ok(Scan {
+        lf,
+        format,
+        warnings,
+        files: 1,
+        compression,
+        spills: spill.into_iter().collect(),
+    })
+}
+
+/// Parquet files carry their own schema, so dtypes are taken as-is instead of
+/// being re-inferred.
+fn read_parquet(path: &str) -> Result<DataFrame> {
+    Ok(ParquetReader::new(open(path)?).finish()?)
+}
+
+/// Reads JSON Lines and flattens nested objects into dotted column names.
+///
+/// When lines disagree on a field's type the whole file is used for schema
+/// inference so polars can settle on a common supertype.
+fn read_ndjson<R: MmapBytesReader>(source: R, full_inference: bool) -> Result<DataFrame> {
+    let mut reader = JsonLineReader::new(source);
+    if full_inference {
+        reader = reader.infer_schema_len(None);
+    }
+    let df = reader.finish()?;
+
+    let mut columns = Vec::with_capacity(df.width());
+    for col in df.get_columns() {
+        flatten_struct(col.as_materialized_series().clone(), &mut columns)?;
+    }
+    Ok(DataFrame::new(columns)?)
+}
+
+fn flatten_struct(series: Series, out: &mut Vec<Column>) -> PolarsResult<()> {
+    if !matches!(series.dtype(), DataType::Struct(_)) {
+        out.push(series.into_column());
+        return Ok(());
+    }
+
+    // Push the parent's nulls down so a missing object shows up as missing
+    // fields rather than silently disappearing.
+    let series = series.propagate_nulls().unwrap_or(series);
+    let parent = series.name().clone();
+    for field in series.struct_()?.fields_as_series() {
+        let name = format!("{}.{}", parent, field.name());
+        flatten_struct(field.with_name(name.into()), out)?;
+    }
+    Ok(())
+}
+
+/// Lazy counterpart of [`flatten_struct`].
+fn flatten_struct_expr(expr: Expr, name: &str, dtype: &DataType, out: &mut Vec<Expr>) {
+    let DataType::Struct(fields) = dtype else {
+        out.push(expr.alias(name));
+        return;
+    };
+
+    for field in fields {
+        let child = format!("{}.{}", name, field.name());
+        let field_expr = expr.clone().struct_().field_by_name(field.name());
+        flatten_struct_expr(field_expr, &child, field.dtype(), out);
+    }
+}
+
+/// Scans every line and reports fields whose JSON type differs between lines.
+/// Nulls are ignored, since they are compatible with any type.
+fn ndjson_schema_conflicts(reader: impl BufRead) -> Result<Vec<String>> {
+    // field -> JSON type -> first line it was seen on
+    let mut seen: BTreeMap<String, BTreeMap<&'static str, usize>> = BTreeMap::new();
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: Value = serde_json::from_str(&line)
+            .with_context(|| format!("invalid JSON on line {}", idx + 1))?;
+        let Value::Object(map) = value else {
+            bail!("line {} is not a JSON object", idx + 1);
+        };
+        record_json_types("", &map, idx + 1, &mut seen);
+    }
+
+    Ok(seen
+        .into_iter()
+        .filter(|(_, types)| types.len() > 1)
+        .map(|(field, types)| {
+            let found = types
+                .iter()
+                .map(|(ty, line)| format!("{} (line {})", ty, line))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("'{}' has conflicting types: {}", field, found)
+        })
+        .collect())
+}
+
+fn record_json_types(
+    prefix: &str,
+    map: &serde_json::Map<String, Value>,
+    line: usize,
+    seen: &mut BTreeMap<String, BTreeMap<&'static str, usize>>,
+) {
+    for (key, value) in map {
+        let field = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        let ty = match value {
+            Value::Null => continue,
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        seen.entry(field.clone())
+            .or_default()
+            .entry(ty)
+            .or_insert(line);
+
+        if let Value::Object(nested) = value {
+            record_json_types(&field, nested, line, seen);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+
+    use flate2::Compression as Level;
+    use flate2::write::GzEncoder;
+
+    use super::*;
+
+    #[test]
+    fn latin1_csv_is_sampled_from_a_full_read() {
+        let path = std::env::temp_dir().join(format!("mlcheck-latin1-{}.csv", std::process::id()));
+        fs::write(&path, b"city,n\ncaf\xe9,1\nna\xefve,2\n").unwrap();
+        let path = path.to_str().unwrap().to_string();
+        let csv = CsvOptions {
+            encoding: Encoding::Latin1,
+            ..CsvOptions::default()
+        };
+        let scannable = scannable(&path, &csv);
+        let sampled = read_dataset_sample(&path, None, &csv, SampleSize::Rows(1), 7);
+        fs::remove_file(&path).unwrap();
+
+        assert!(!scannable.unwrap());
+        let (dataset, sample) = sampled.unwrap();
+        assert_eq!(dataset.df.height(), 1);
+        assert_eq!(sample.source_rows.len(), 1);
+    }
+
+    #[test]
+    fn compressed_csv_is_sampled_without_spilling() {
+        let path =
+            std::env::temp_dir().join(format!("mlcheck-sample-{}.csv.gz", std::process::id()));
+        let mut encoder = GzEncoder::new(Vec::new(), Level::default());
+        encoder.write_all(b"a,b\n1,x\n2,y\n3,z\n").unwrap();
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+        let path = path.to_str().unwrap().to_string();
+        let csv = CsvOptions::default();
+        let scannable = scannable(&path, &csv);
+        let sampled = read_dataset_sample(&path, None, &csv, SampleSize::Rows(2), 7);
+        fs::remove_file(&path).unwrap();
+
+        assert!(!scannable.unwrap());
+        let (dataset, _) = sampled.unwrap();
+        assert_eq!(dataset.df.height(), 2);
+        assert_eq!(dataset.compression, Some(Compression::Gzip));
+    }
+}
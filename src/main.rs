@@ -1,140 +1,662 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use polars::prelude::*;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Result, bail};
+use clap::{Args, Parser, Subcommand};
+
+use mlcheck::budget::Budget;
+use mlcheck::check::{
+    CheckKind, ConstantColumnsCheck, DuplicatesCheck, IdentifierCheck, MissingValuesCheck,
+    OutlierCheck,
+};
+use mlcheck::config::Config;
+use mlcheck::contract::Contract;
+use mlcheck::dialect::{self, CsvOptions, Encoding};
+use mlcheck::drift::{self, DriftFormat, DriftReport, DriftThresholds};
+use mlcheck::html::MissingMap;
+use mlcheck::leakage::{self, LeakageFormat, LeakageReport};
+use mlcheck::loader::{Dataset, Format, read_dataset, read_dataset_sample, scan_dataset};
+use mlcheck::outliers::OutlierMethod;
+use mlcheck::profile;
+use mlcheck::report::{
+    InspectFormat, InspectReport, OutputFormat, Overview, REPORT_SCHEMA_VERSION, Severity,
+    percentage, print_load_warnings,
+};
+use mlcheck::sample::{self, Sample, SampleSize};
+use mlcheck::streaming;
+use mlcheck::target::{self, TargetOptions, TargetTask};
+use mlcheck::validate::{self, ValidateOptions};
 
 #[derive(Parser)]
 #[command(name = "mlcheck")]
 #[command(about = "Fast ML dataset validation CLI built in Rust - catch data issues before training", long_about=None)]
 #[command(version)]
 struct Cli {
+    /// Project config file [default: mlcheck.toml in the working directory, if any]
+    #[arg(long, global = true)]
+    config: Option<String>,
+    #[command(flatten)]
+    csv: CsvArgs,
     #[command(subcommand)]
     command: Commands,
 }
 
+/// CSV dialect flags; they override the `[csv]` section of the config file.
+#[derive(Args)]
+#[command(next_help_heading = "CSV options")]
+struct CsvArgs {
+    /// Field delimiter, e.g. ';' or tab [default: detected from comma, tab, semicolon and pipe]
+    #[arg(long, global = true, value_parser = dialect::parse_char)]
+    delimiter: Option<char>,
+    /// Quote character [default: "]
+    #[arg(long, global = true, value_parser = dialect::parse_char)]
+    quote_char: Option<char>,
+    /// Skip lines starting with this prefix, e.g. '#'
+    #[arg(long, global = true)]
+    comment_prefix: Option<String>,
+    /// Comma-separated values read as missing, e.g. NA,NULL,?
+    #[arg(long, global = true, value_delimiter = ',')]
+    null_values: Vec<String>,
+    /// Lines to skip before the header
+    #[arg(long, global = true)]
+    skip_rows: Option<usize>,
+    /// The first line is data, not column names
+    #[arg(long, global = true)]
+    no_header: bool,
+    /// Text encoding of the file [default: utf8]
+    #[arg(long, global = true, value_enum)]
+    encoding: Option<Encoding>,
+    /// Rows used to infer column types; 0 scans the whole file [default: 100]
+    #[arg(long, global = true)]
+    infer_schema_length: Option<usize>,
+}
+
+impl CsvArgs {
+    fn apply(&self, mut csv: CsvOptions) -> CsvOptions {
+        if let Some(delimiter) = self.delimiter {
+            csv.delimiter = Some(delimiter);
+        }
+        if let Some(quote_char) = self.quote_char {
+            csv.quote_char = quote_char;
+        }
+        if let Some(prefix) = &self.comment_prefix {
+            csv.comment_prefix = Some(prefix.clone());
+        }
+        if !self.null_values.is_empty() {
+            csv.null_values = self.null_values.clone();
+        }
+        if let Some(skip_rows) = self.skip_rows {
+            csv.skip_rows = skip_rows;
+        }
+        if self.no_header {
+            csv.has_header = false;
+        }
+        if let Some(encoding) = self.encoding {
+            csv.encoding = encoding;
+        }
+        if let Some(length) = self.infer_schema_length {
+            csv.infer_schema_length = length;
+        }
+        csv
+    }
+}
+
+/// Number of most frequent values shown per text column when profiling.
+const DEFAULT_TOP_K: usize = 5;
+
 #[derive(Subcommand)]
 enum Commands {
-    Inspect {
-        file: String,
-    },
-    Validate {
-        file: String,
-        #[arg(short, long)]
-        target: Option<String>,
-    },
+    Inspect(InspectArgs),
+    Validate(Box<ValidateArgs>),
+    /// Compare the distribution of every shared column between two files
+    Drift(DriftArgs),
+    /// Find test rows that also appear in the train file; exits non-zero on overlap
+    Leakage(LeakageArgs),
+    /// Write a starter schema contract describing a dataset
+    InferSchema(InferSchemaArgs),
+}
+
+#[derive(Args)]
+struct InspectArgs {
+    /// Data file, directory of partitioned files, quoted glob such as 'data/*.csv', or - for stdin
+    file: String,
+    /// Format of the input instead of detecting it from the extension or contents
+    #[arg(long, value_enum)]
+    input_format: Option<Format>,
+    /// Profile every column: numeric summaries, top values, date ranges
+    #[arg(short, long)]
+    profile: bool,
+    /// Number of most frequent values shown for text columns when profiling [default: 5]
+    #[arg(long)]
+    top_k: Option<usize>,
+    /// Output format; json and html always include column profiles [default: text]
+    #[arg(long, value_enum)]
+    format: Option<InspectFormat>,
+    /// Write the rendered report to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Comma-separated columns to leave out, e.g. row ids
+    #[arg(long, value_delimiter = ',')]
+    ignore_columns: Vec<String>,
+    #[command(flatten)]
+    sample: SampleArgs,
+}
+
+#[derive(Args)]
+struct SampleArgs {
+    /// Check a random sample of this many rows instead of the whole file
+    #[arg(long, conflicts_with = "sample_frac")]
+    sample: Option<usize>,
+    /// Check a random fraction of the rows, e.g. 0.01 for 1%
+    #[arg(long, value_parser = sample::parse_fraction)]
+    sample_frac: Option<f64>,
+    /// Seed for the random sample; the same seed picks the same rows
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
+impl SampleArgs {
+    fn size(&self) -> Option<SampleSize> {
+        match (self.sample, self.sample_frac) {
+            (Some(rows), _) => Some(SampleSize::Rows(rows)),
+            (None, Some(fraction)) => Some(SampleSize::Fraction(fraction)),
+            (None, None) => None,
+        }
+    }
+
+    /// Reads the dataset, only the sampled rows of it when `--sample` or
+    /// `--sample-frac` was given.
+    fn read(
+        &self,
+        path: &str,
+        format: Option<Format>,
+        csv: &CsvOptions,
+    ) -> Result<(Dataset, Option<Sample>)> {
+        match self.size() {
+            Some(size) => {
+                let (dataset, sample) = read_dataset_sample(path, format, csv, size, self.seed)?;
+                Ok((dataset, Some(sample)))
+            }
+            None => Ok((read_dataset(path, format, csv)?, None)),
+        }
+    }
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    /// Data file, directory of partitioned files, quoted glob such as 'data/*.csv', or - for stdin.
+    /// Compressed files are decompressed in memory unless --streaming is given
+    file: String,
+    /// Format of the input instead of detecting it from the extension or contents
+    #[arg(long, value_enum)]
+    input_format: Option<Format>,
+    #[arg(short, long)]
+    target: Option<String>,
+    /// Output format of the report [default: text]
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+    /// Write the rendered report to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Exit with a non-zero status when a finding of this severity or worse is raised [default: error]
+    #[arg(long, value_enum)]
+    fail_on: Option<Severity>,
+    /// Comma-separated checks to run [default: all]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    checks: Vec<CheckKind>,
+    /// Comma-separated columns to leave out of every check, e.g. row ids
+    #[arg(long, value_delimiter = ',')]
+    ignore_columns: Vec<String>,
+    /// Missing-value percentage above which a column is reported as an error
+    #[arg(long)]
+    max_missing_pct: Option<f64>,
+    /// Duplicate-row percentage above which the dataset is reported as an error
+    #[arg(long)]
+    max_duplicate_pct: Option<f64>,
+    /// Method used to flag outliers in numeric columns [default: iqr]
+    #[arg(long, value_enum)]
+    outlier_method: Option<OutlierMethod>,
+    /// Cut-off for the outlier method [default: 1.5 for iqr, 3.0 for zscore, 3.5 for mad]
+    #[arg(long)]
+    outlier_threshold: Option<f64>,
+    /// Outlier percentage above which a column is reported as an error
+    #[arg(long)]
+    max_outlier_pct: Option<f64>,
+    /// Share of rows, in percent, above which a column's most frequent value makes it near-constant [default: 99.5]
+    #[arg(long)]
+    max_dominant_pct: Option<f64>,
+    /// Distinct values, as a percentage of non-null rows, above which an integer or text column is reported as an identifier [default: 95]
+    #[arg(long)]
+    max_unique_pct: Option<f64>,
+    /// Treat the target as classification or regression instead of guessing
+    #[arg(long, value_enum)]
+    task: Option<TargetTask>,
+    /// Classes with fewer rows than this are reported as rare [default: 10]
+    #[arg(long)]
+    min_class_count: Option<usize>,
+    /// Majority/minority class ratio above which the target is reported as an error
+    #[arg(long)]
+    max_imbalance_ratio: Option<f64>,
+    /// Test fraction used to check that every class survives a stratified split [default: 0.2]
+    #[arg(long, value_parser = target::parse_test_size)]
+    test_size: Option<f64>,
+    /// YAML contract declaring expected columns, dtypes and value constraints
+    #[arg(long, conflicts_with = "streaming")]
+    schema: Option<String>,
+    /// Scan the file with the polars streaming engine instead of loading it;
+    /// outlier and target-leakage detection are skipped. Compressed
+    /// files are decompressed into --spill-dir first, which needs disk space
+    /// for the uncompressed data
+    #[arg(long, conflicts_with_all = ["sample", "sample_frac"])]
+    streaming: bool,
+    /// Cap on heap memory for the run, e.g. 512MB or 2GB. Streaming batches are sized
+    /// to fit; deduplication and distinct counts still grow with the data, and a run
+    /// that needs more than the cap stops with an error instead of exhausting the machine
+    #[arg(long, requires = "streaming", value_parser = streaming::parse_size)]
+    memory_limit: Option<u64>,
+    /// Directory for decompressed copies of compressed input [default: the
+    /// system temp directory]. Copies are deleted when the run ends; a killed
+    /// run leaves its mlcheck-<pid>-* files behind
+    #[arg(long, requires = "streaming")]
+    spill_dir: Option<PathBuf>,
+    #[command(flatten)]
+    sample: SampleArgs,
+}
+
+#[derive(Args)]
+struct DriftArgs {
+    /// Reference dataset, usually the training split
+    train: String,
+    /// Dataset compared against the reference, e.g. the test split
+    test: String,
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = DriftFormat::Text)]
+    format: DriftFormat,
+    /// PSI at or above which a numeric column counts as drifted
+    #[arg(long, default_value_t = 0.2)]
+    psi_threshold: f64,
+    /// Jensen-Shannon distance at or above which a categorical column counts as drifted
+    #[arg(long, default_value_t = 0.1)]
+    js_threshold: f64,
+    /// Number of quantile bins used for PSI
+    #[arg(long, default_value_t = 10)]
+    bins: usize,
 }
 
-fn main() -> Result<()> {
+#[derive(Args)]
+struct LeakageArgs {
+    /// Training split
+    train: String,
+    /// Held-out split checked for rows seen in training
+    test: String,
+    /// Comma-separated columns identifying a row [default: all shared columns except lists and structs]
+    #[arg(long, value_delimiter = ',')]
+    keys: Vec<String>,
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = LeakageFormat::Text)]
+    format: LeakageFormat,
+}
+
+#[derive(Args)]
+struct InferSchemaArgs {
+    file: String,
+    /// Where to write the contract; printed to stdout when omitted
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Text columns with at most this many distinct values get an allowed-value set
+    #[arg(long, default_value_t = 20)]
+    max_allowed_values: usize,
+}
+
+/// Counts heap memory so `--memory-limit` can be enforced.
+#[global_allocator]
+static ALLOCATOR: Budget = Budget::new();
+
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
+    let mut config = Config::discover(cli.config.as_deref())?;
+    config.csv = cli.csv.apply(config.csv);
 
     match cli.command {
-        Commands::Inspect { file } => {
-            inspect_dataset(&file)?;
+        Commands::Inspect(args) => {
+            inspect_dataset(&args, &config)?;
+        }
+        Commands::Validate(args) => {
+            if !validate_dataset(&args, &config)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Commands::Drift(args) => {
+            drift_datasets(&args, &config)?;
+        }
+        Commands::Leakage(args) => {
+            if !find_leakage(&args, &config)? {
+                return Ok(ExitCode::FAILURE);
+            }
         }
-        Commands::Validate { file, target } => {
-            validate_dataset(&file, target.as_deref())?;
+        Commands::InferSchema(args) => {
+            infer_schema(&args, &config)?;
         }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-fn read_csv(path: &str) -> PolarsResult<DataFrame> {
-    CsvReadOptions::default()
-        .with_has_header(true)
-        .try_into_reader_with_file_path(Some(path.into()))?
-        .finish()
-}
+fn inspect_dataset(args: &InspectArgs, config: &Config) -> Result<()> {
+    let format = args
+        .format
+        .or(config.inspect.format)
+        .unwrap_or(InspectFormat::Text);
+    let top_k = args.top_k.or(config.inspect.top_k).unwrap_or(DEFAULT_TOP_K);
+    check_output(format == InspectFormat::Text, args.output.as_deref())?;
+
+    let path = args.file.as_str();
+    let (mut dataset, sample) = args.sample.read(path, args.input_format, &config.csv)?;
+    dataset.df = dataset
+        .df
+        .drop_many(ignored_columns(&args.ignore_columns, config));
+    if format != InspectFormat::Text {
+        return inspect_report(args, dataset, sample, format, top_k);
+    }
 
-fn inspect_dataset(path: &str) -> Result<()> {
     println!("🔍 Inspecting: {}\n", path);
 
-    let df = read_csv(path)?;
+    let Dataset {
+        df,
+        format,
+        warnings,
+        files,
+        compression,
+    } = dataset;
+    print_load_warnings(&warnings);
 
     println!("📊 Dataset Overview");
+    match compression {
+        Some(compression) => println!("├─ Format: {} ({})", format, compression),
+        None => println!("├─ Format: {}", format),
+    }
+    if files > 1 {
+        println!("├─ Files: {}", files);
+    }
     println!("├─ Rows: {}", df.height());
     println!("├─ Columns: {}", df.width());
-    println!(
-        "└─ Memory: {:.2} MB",
-        df.estimated_size() as f64 / 1_000_000.0
-    );
+    match &sample {
+        Some(sample) => {
+            println!(
+                "├─ Memory: {:.2} MB",
+                df.estimated_size() as f64 / 1_000_000.0
+            );
+            println!("└─ {}", sample.info);
+        }
+        None => println!(
+            "└─ Memory: {:.2} MB",
+            df.estimated_size() as f64 / 1_000_000.0
+        ),
+    }
 
     println!("\n📋 Columns:");
+    let profile = args.profile || config.inspect.profile.unwrap_or(false);
     for col in df.get_columns() {
-        println!("├─ {} ({})", col.name(), col.dtype());
+        if profile {
+            profile::profile_column(col, top_k)?.print();
+        } else {
+            println!("├─ {} ({})", col.name(), col.dtype());
+        }
     }
 
     Ok(())
 }
 
-fn validate_dataset(path: &str, target: Option<&str>) -> Result<()> {
-    println!("✓ Validating: {}\n", path);
+/// Inspect as a rendered json or html report, with every column profiled.
+fn inspect_report(
+    args: &InspectArgs,
+    dataset: Dataset,
+    sample: Option<Sample>,
+    output_format: InspectFormat,
+    top_k: usize,
+) -> Result<()> {
+    let Dataset {
+        df,
+        format,
+        warnings,
+        ..
+    } = dataset;
 
-    let df = read_csv(path)?;
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(|col| profile::profile_column(col, top_k))
+        .collect::<Result<_>>()?;
+    let missing_map = match output_format {
+        InspectFormat::Html => Some(MissingMap::from_frame(&df)?),
+        _ => None,
+    };
 
-    // Basic Info
-    println!("📊 Dataset Overview");
-    println!("├─ Shape: {} rows × {} columns", df.height(), df.width());
-    println!(
-        "└─ Size: {:.2} MB\n",
-        df.estimated_size() as f64 / 1_000_000.0
-    );
+    let report = InspectReport {
+        schema_version: REPORT_SCHEMA_VERSION,
+        file: args.file.clone(),
+        format: format.to_string(),
+        warnings,
+        overview: Overview {
+            rows: df.height(),
+            columns: df.width(),
+            size_bytes: df.estimated_size(),
+        },
+        sample: sample.map(|s| s.info),
+        columns,
+        missing_map,
+    };
+    report.write(output_format, args.output.as_deref())
+}
 
-    // Check missing values
-    println!("🔍 Missing Values:");
-    let mut has_missing = false;
+/// Columns given with `--ignore-columns`, or else those from the config file.
+fn ignored_columns<'a>(flag: &'a [String], config: &'a Config) -> &'a [String] {
+    if flag.is_empty() {
+        &config.ignore_columns
+    } else {
+        flag
+    }
+}
 
-    for col in df.get_columns() {
-        let null_count = col.null_count();
-        if null_count > 0 {
-            has_missing = true;
-            let percentage = (null_count as f64 / df.height() as f64) * 100.0;
-            println!("├─ {}: {} ({:.1}%)", col.name(), null_count, percentage);
-        }
+/// Text output goes straight to the terminal, so only rendered formats can be
+/// written to a file.
+fn check_output(text: bool, output: Option<&str>) -> Result<()> {
+    if text && output.is_some() {
+        bail!("--output needs a rendered format such as json or html");
     }
+    Ok(())
+}
 
-    if !has_missing {
-        println!("└─ ✓ No missing values");
+/// Runs all checks and prints the report. Returns whether the dataset passed
+/// the `--fail-on` gate.
+fn validate_dataset(args: &ValidateArgs, config: &Config) -> Result<bool> {
+    let format = args
+        .format
+        .or(config.validate.format)
+        .unwrap_or(OutputFormat::Text);
+    check_output(format == OutputFormat::Text, args.output.as_deref())?;
+
+    let mut options = validate_options(args, config);
+    options.visuals = format == OutputFormat::Html;
+    let report = match options.memory_limit {
+        Some(limit) => {
+            ALLOCATOR.set_limit(limit);
+            let scan = scan_dataset(
+                &args.file,
+                options.input_format,
+                &options.csv,
+                Some(&options.spill_dir()),
+            )?;
+            let schema = scan.lf.clone().collect_schema()?;
+            let rows = streaming::morsel_rows(limit, &schema)?;
+            // SAFETY: the CLI runs on this thread alone; polars' workers sit
+            // idle between queries, and no streaming query has run yet.
+            unsafe { streaming::set_morsel_rows(rows) };
+            validate::validate_scan(&args.file, scan, &options)?
+        }
+        None => validate::validate_path(&args.file, &options)?,
+    };
+    report.print(format, args.output.as_deref())?;
+    Ok(report.passed)
+}
+
+/// Flags take precedence over the config file, which takes precedence over
+/// the built-in defaults.
+fn validate_options(args: &ValidateArgs, config: &Config) -> ValidateOptions {
+    let defaults = ValidateOptions::default();
+    let cfg = &config.validate;
+
+    ValidateOptions {
+        target: args.target.clone().or_else(|| cfg.target.clone()),
+        target_options: TargetOptions {
+            task: args.task.or(cfg.task),
+            min_class_count: args
+                .min_class_count
+                .or(cfg.min_class_count)
+                .unwrap_or(defaults.target_options.min_class_count),
+            test_size: args
+                .test_size
+                .or(cfg.test_size)
+                .unwrap_or(defaults.target_options.test_size),
+            leakage: true,
+        },
+        max_imbalance_ratio: args.max_imbalance_ratio.or(cfg.max_imbalance_ratio),
+        missing: MissingValuesCheck {
+            max_pct: args.max_missing_pct.or(cfg.max_missing_pct),
+        },
+        duplicates: DuplicatesCheck {
+            max_pct: args.max_duplicate_pct.or(cfg.max_duplicate_pct),
+        },
+        outliers: OutlierCheck {
+            method: args
+                .outlier_method
+                .or(cfg.outlier_method)
+                .unwrap_or(defaults.outliers.method),
+            threshold: args.outlier_threshold.or(cfg.outlier_threshold),
+            max_pct: args.max_outlier_pct.or(cfg.max_outlier_pct),
+        },
+        constant: ConstantColumnsCheck {
+            max_dominant_pct: args
+                .max_dominant_pct
+                .or(cfg.max_dominant_pct)
+                .unwrap_or(defaults.constant.max_dominant_pct),
+        },
+        identifiers: IdentifierCheck {
+            max_unique_pct: args
+                .max_unique_pct
+                .or(cfg.max_unique_pct)
+                .unwrap_or(defaults.identifiers.max_unique_pct),
+            target: None,
+        },
+        schema: args.schema.clone().or_else(|| cfg.schema.clone()),
+        checks: match (&args.checks, &cfg.checks) {
+            (flag, _) if !flag.is_empty() => flag.clone(),
+            (_, Some(checks)) => checks.clone(),
+            _ => defaults.checks,
+        },
+        ignore_columns: ignored_columns(&args.ignore_columns, config).to_vec(),
+        input_format: args.input_format,
+        csv: config.csv.clone(),
+        fail_on: args.fail_on.or(cfg.fail_on).unwrap_or(defaults.fail_on),
+        sample: args.sample.size(),
+        seed: args.sample.seed,
+        streaming: args.streaming,
+        memory_limit: args.memory_limit,
+        spill_dir: args.spill_dir.clone(),
+        visuals: false,
     }
+}
 
-    // Check duplicates
-    println!("\n🔁 Duplicates:");
+fn drift_datasets(args: &DriftArgs, config: &Config) -> Result<()> {
+    let train = read_dataset(&args.train, None, &config.csv)?;
+    let test = read_dataset(&args.test, None, &config.csv)?;
 
-    let lf = df.clone().lazy();
-    let deduped = lf.unique(None, UniqueKeepStrategy::First).collect()?;
+    let thresholds = DriftThresholds {
+        psi: args.psi_threshold,
+        js_distance: args.js_threshold,
+        bins: args.bins,
+    };
+    let (schema_mismatches, columns, not_compared) =
+        drift::compare(&train.df, &test.df, &thresholds)?;
 
-    let duplicates = df.height() - deduped.height();
+    let warnings = split_warnings([(&args.train, train.warnings), (&args.test, test.warnings)]);
 
-    if duplicates > 0 {
-        println!(
-            "└─ ⚠️  {} duplicate rows ({:.1}%)",
-            duplicates,
-            (duplicates as f64 / df.height() as f64) * 100.0
-        );
-    } else {
-        println!("└─ ✓ No duplicates");
+    let report = DriftReport {
+        schema_version: REPORT_SCHEMA_VERSION,
+        train: args.train.clone(),
+        test: args.test.clone(),
+        warnings,
+        schema_mismatches,
+        columns,
+        not_compared,
+    };
+    report.print(args.format)
+}
+
+/// Load warnings of both splits, each prefixed with its file the way shard
+/// warnings are.
+fn split_warnings(splits: [(&str, Vec<String>); 2]) -> Vec<String> {
+    splits
+        .into_iter()
+        .flat_map(|(path, warnings)| {
+            warnings
+                .into_iter()
+                .map(move |warning| format!("{}: {}", path, warning))
+        })
+        .collect()
+}
+
+/// Prints the leakage report. Returns whether the splits are disjoint.
+fn find_leakage(args: &LeakageArgs, config: &Config) -> Result<bool> {
+    let train = read_dataset(&args.train, None, &config.csv)?;
+    let test = read_dataset(&args.test, None, &config.csv)?;
+
+    let overlap = leakage::find_overlap(&train.df, &test.df, &args.keys)?;
+
+    let warnings = split_warnings([(&args.train, train.warnings), (&args.test, test.warnings)]);
+
+    let report = LeakageReport {
+        schema_version: REPORT_SCHEMA_VERSION,
+        train: args.train.clone(),
+        test: args.test.clone(),
+        warnings,
+        keys: overlap.keys,
+        nested_columns: overlap.nested_columns,
+        test_rows: test.df.height(),
+        leaked_rows: overlap.leaked_rows,
+        percentage: percentage(overlap.leaked_rows, test.df.height()),
+        leaked_keys: overlap.leaked_keys,
+        examples: overlap.examples,
+    };
+    report.print(args.format)?;
+    Ok(report.leaked_rows == 0)
+}
+
+fn infer_schema(args: &InferSchemaArgs, config: &Config) -> Result<()> {
+    let Dataset { df, warnings, .. } = read_dataset(&args.file, None, &config.csv)?;
+    let contract = Contract::infer(&df, args.max_allowed_values)?;
+
+    let mut yaml = format!(
+        "# Inferred by mlcheck from {} ({} rows). Review before committing:\n\
+         # ranges and value sets only reflect this snapshot.\n",
+        args.file,
+        df.height()
+    );
+    for warning in &warnings {
+        yaml.push_str(&format!("# warning: {}\n", warning));
     }
+    yaml.push_str(&contract.to_yaml()?);
 
-    // Target column analysis
-    if let Some(target_col) = target {
-        println!("\n🎯 Target Column: {}", target_col);
-
-        if let Ok(series) = df.column(target_col) {
-            println!("├─ Type: {:?}", series.dtype());
-            println!("├─ Unique values: {}", series.n_unique()?);
-
-            let null_count = series.null_count();
-            if null_count > 0 {
-                println!(
-                    "└─ ⚠️  Missing in target: {} ({:.1}%)",
-                    null_count,
-                    (null_count as f64 / df.height() as f64) * 100.0
-                );
-            } else {
-                println!("└─ ✓ No missing values in target");
-            }
-        } else {
-            println!("└─ ❌ Target column '{}' not found!", target_col);
+    match &args.output {
+        Some(output) => {
+            std::fs::write(output, yaml)?;
+            println!(
+                "📜 Wrote contract for {} columns to {}",
+                contract.columns.len(),
+                output
+            );
         }
+        None => print!("{}", yaml),
     }
 
     Ok(())
@@ -0,0 +1,300 @@
+use crate::check::CheckKind;
+use crate::report::{
+    MAX_CLASSES_SHOWN, Severity, ValidationReport, format_percentage, format_quantiles,
+};
+use crate::target::TargetReport;
+
+/// Hidden marker a bot can search for to update its previous comment instead
+/// of posting a new one.
+const COMMENT_MARKER: &str = "<!-- mlcheck-report -->";
+
+/// Renders the report as GitHub-flavoured Markdown for a PR comment: a status
+/// headline and the findings up front, per-check tables below.
+pub fn validation_markdown(report: &ValidationReport) -> String {
+    let mut md = format!("{}\n", COMMENT_MARKER);
+
+    let status = if report.passed {
+        "✅ passed"
+    } else {
+        "❌ failed"
+    };
+    md.push_str(&format!(
+        "## mlcheck: {} {} (fail-on: {})\n\n",
+        code(&report.file),
+        status,
+        report.fail_on
+    ));
+    if let Some(sample) = &report.sample {
+        md.push_str(&format!("> [!NOTE]\n> {}\n\n", sample));
+    }
+    md.push_str(&format!(
+        "| Rows | Columns | Size |\n|---:|---:|---:|\n| {} | {} | {:.2} MB |\n\n",
+        report.overview.rows,
+        report.overview.columns,
+        report.overview.size_bytes as f64 / 1_000_000.0
+    ));
+    if let Some(spilled) = &report.execution.spilled {
+        md.push_str(&format!(
+            "> [!NOTE]\n> Streaming {}\n\n",
+            cell(&spilled.to_string())
+        ));
+    }
+
+    md.push_str("### Findings\n\n");
+    if report.findings.is_empty() {
+        md.push_str("No findings.\n\n");
+    } else {
+        md.push_str("| | Check | Column | Message |\n|---|---|---|---|\n");
+        for finding in &report.findings {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                severity_icon(finding.severity),
+                finding.check,
+                cell(finding.column.as_deref().unwrap_or("")),
+                cell(&finding.message)
+            ));
+        }
+        md.push('\n');
+    }
+
+    md.push_str("### Missing values\n\n");
+    match &report.missing {
+        None => md.push_str(&format!("{}.\n\n", report.skip_reason(CheckKind::Missing))),
+        Some(missing) if missing.iter().all(|m| m.count == 0) => {
+            md.push_str("No missing values.\n\n")
+        }
+        Some(missing) => {
+            md.push_str("| Column | Missing | % |\n|---|---:|---:|\n");
+            for m in missing.iter().filter(|m| m.count > 0) {
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    cell(&m.column),
+                    m.count,
+                    format_percentage(m.percentage, m.ci.as_ref())
+                ));
+            }
+            md.push('\n');
+        }
+    }
+
+    md.push_str("### Duplicates\n\n");
+    match &report.duplicates {
+        Some(duplicates) if duplicates.sampled => md.push_str(&format!(
+            "| Duplicate rows | % of sample |\n|---:|---:|\n| ≥ {} | {:.1}% |\n\nSampled: a duplicate only shows when both copies are drawn, so the file has at least this many and likely a higher rate.\n\n",
+            duplicates.count, duplicates.percentage
+        )),
+        Some(duplicates) => md.push_str(&format!(
+            "| Duplicate rows | % |\n|---:|---:|\n| {} | {:.1}% |\n\n",
+            duplicates.count, duplicates.percentage
+        )),
+        None => md.push_str(&format!(
+            "{}.\n\n",
+            report.skip_reason(CheckKind::Duplicates)
+        )),
+    }
+
+    if let Some(outliers) = &report.outliers {
+        let flagged: Vec<_> = outliers.columns.iter().filter(|c| c.count > 0).collect();
+        if !flagged.is_empty() {
+            md.push_str(&format!(
+                "<details><summary>Outliers ({}, k={}) in {} columns</summary>\n\n| Column | Outliers | % | Accepted range |\n|---|---:|---:|---|\n",
+                outliers.method,
+                outliers.threshold,
+                flagged.len()
+            ));
+            for c in flagged {
+                md.push_str(&format!(
+                    "| {} | {} | {:.1}% | [{:.3}, {:.3}] |\n",
+                    cell(&c.column),
+                    c.count,
+                    c.percentage,
+                    c.lower,
+                    c.upper
+                ));
+            }
+            md.push_str("\n</details>\n\n");
+        }
+    }
+
+    if let Some(constant) = &report.constant
+        && !constant.columns.is_empty()
+    {
+        md.push_str(&format!(
+            "<details><summary>Constant columns (dominant value > {}%): {}</summary>\n\n| Column | Dominant value | Rows | % | Distinct |\n|---|---|---:|---:|---:|\n",
+            constant.max_dominant_pct,
+            constant.columns.len()
+        ));
+        for c in &constant.columns {
+            md.push_str(&format!(
+                "| {} | {} | {} | {:.1}% | {} |\n",
+                cell(&c.column),
+                cell(c.value.as_deref().unwrap_or("null")),
+                c.count,
+                c.percentage,
+                c.unique
+            ));
+        }
+        md.push_str("\n</details>\n\n");
+    }
+
+    if let Some(identifiers) = &report.identifiers
+        && !identifiers.columns.is_empty()
+    {
+        md.push_str(&format!(
+            "<details><summary>ID-like columns (distinct values > {}%): {}</summary>\n\n| Column | Looks like | Distinct | % |\n|---|---|---:|---:|\n",
+            identifiers.max_unique_pct,
+            identifiers.columns.len()
+        ));
+        for c in &identifiers.columns {
+            md.push_str(&format!(
+                "| {} | {} | {} | {:.1}% |\n",
+                cell(&c.column),
+                c.kind,
+                c.unique,
+                c.percentage
+            ));
+        }
+        md.push_str("\n</details>\n\n");
+    }
+
+    if let Some(target) = &report.target {
+        md.push_str(&target_markdown(target));
+    }
+
+    if let Some(contract) = &report.contract
+        && !contract.violations.is_empty()
+    {
+        md.push_str(&format!(
+            "### Schema contract {}\n\n| Column | Rule | Message |\n|---|---|---|\n",
+            code(&contract.contract)
+        ));
+        for v in &contract.violations {
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                cell(&v.column),
+                v.rule,
+                cell(&v.message)
+            ));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+fn target_markdown(target: &TargetReport) -> String {
+    let mut md = format!("### Target {}\n\n", code(&target.column));
+    if !target.found {
+        md.push_str("❌ Target column not found.\n\n");
+        return md;
+    }
+
+    md.push_str(&format!(
+        "| Type | Unique | Missing |\n|---|---:|---:|\n| {} | {} | {} ({:.1}%) |\n\n",
+        target.dtype.as_deref().unwrap_or("?"),
+        target.unique.unwrap_or(0),
+        target.missing.unwrap_or(0),
+        target.missing_percentage.unwrap_or(0.0)
+    ));
+
+    if let Some(classes) = &target.classes {
+        md.push_str(&format!(
+            "Classification, imbalance ratio {:.2}\n\n| Class | Count | % |\n|---|---:|---:|\n",
+            classes.imbalance_ratio
+        ));
+        for class in classes.counts.iter().take(MAX_CLASSES_SHOWN) {
+            md.push_str(&format!(
+                "| {} | {} | {:.1}% |\n",
+                cell(&class.class),
+                class.count,
+                class.percentage
+            ));
+        }
+        if classes.counts.len() > MAX_CLASSES_SHOWN {
+            md.push_str(&format!(
+                "| … {} more | | |\n",
+                classes.counts.len() - MAX_CLASSES_SHOWN
+            ));
+        }
+        md.push('\n');
+    } else if let Some(dist) = &target.distribution {
+        md.push_str(&format!(
+            "| Min | Max | Mean | Std | Skewness |\n|---:|---:|---:|---:|---:|\n| {:.3} | {:.3} | {:.3} | {:.3} | {:.3} |\n\nQuantiles: {}\n\n",
+            dist.min,
+            dist.max,
+            dist.mean,
+            dist.std,
+            dist.skewness,
+            format_quantiles(&dist.quantiles)
+        ));
+    }
+
+    md
+}
+
+fn severity_icon(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "ℹ️",
+        Severity::Warn => "⚠️",
+        Severity::Error => "❌",
+    }
+}
+
+/// Keeps user data from breaking out of a table cell or being rendered as
+/// markup or HTML.
+fn cell(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\n' => escaped.push(' '),
+            '\\' | '`' | '|' | '*' | '_' | '[' | ']' | '(' | ')' | '!' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Wraps user data in a code span fenced by more backticks than it contains,
+/// so none of it is read as markdown. Nothing inside needs escaping.
+fn code(text: &str) -> String {
+    let text = text.replace('\n', " ");
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    // A space on each side keeps a leading or trailing backtick from joining
+    // the fence; renderers strip one space from both ends.
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_escapes_markup_and_html() {
+        assert_eq!(
+            cell("a|b `c` <img src=x>&\nd"),
+            "a\\|b \\`c\\` &lt;img src=x&gt;&amp; d"
+        );
+        assert_eq!(
+            cell(r"**bold** _x_ [a](b) ![i](j) #1 C:\d"),
+            r"\*\*bold\*\* \_x\_ \[a\]\(b\) \!\[i\]\(j\) \#1 C:\\d"
+        );
+    }
+
+    #[test]
+    fn code_span_fence_outgrows_backticks_in_the_text() {
+        assert_eq!(code("train.csv"), "`train.csv`");
+        assert_eq!(code("a``b"), "```a``b```");
+        assert_eq!(code("`x"), "`` `x ``");
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::report::percentage;
+use crate::stats;
+
+/// How many offending row indices are kept per column.
+const MAX_EXAMPLES: usize = 5;
+
+/// Scale factor that makes the MAD a consistent estimator of the standard
+/// deviation for normally distributed data.
+const MAD_SCALE: f64 = 0.6745;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutlierMethod {
+    /// Tukey fences: outside [Q1 - k·IQR, Q3 + k·IQR]
+    Iqr,
+    /// Absolute z-score above k
+    Zscore,
+    /// Absolute modified z-score (median absolute deviation) above k
+    Mad,
+}
+
+impl OutlierMethod {
+    pub fn default_threshold(self) -> f64 {
+        match self {
+            OutlierMethod::Iqr => 1.5,
+            OutlierMethod::Zscore => 3.0,
+            OutlierMethod::Mad => 3.5,
+        }
+    }
+}
+
+impl fmt::Display for OutlierMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutlierMethod::Iqr => write!(f, "IQR"),
+            OutlierMethod::Zscore => write!(f, "z-score"),
+            OutlierMethod::Mad => write!(f, "MAD"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Outliers {
+    pub method: OutlierMethod,
+    pub threshold: f64,
+    pub columns: Vec<ColumnOutliers>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnOutliers {
+    pub column: String,
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    pub percentage: f64,
+    /// Zero-based row indices of the first few outliers.
+    pub examples: Vec<usize>,
+}
+
+/// Runs outlier detection on every numeric column of `df`.
+pub fn detect(df: &DataFrame, method: OutlierMethod, threshold: f64) -> Result<Outliers> {
+    let mut columns = Vec::new();
+
+    for col in df.get_columns() {
+        if !col.dtype().is_primitive_numeric() {
+            continue;
+        }
+        if let Some(outliers) = detect_column(col, method, threshold, df.height())? {
+            columns.push(outliers);
+        }
+    }
+
+    Ok(Outliers {
+        method,
+        threshold,
+        columns,
+    })
+}
+
+fn detect_column(
+    col: &Column,
+    method: OutlierMethod,
+    threshold: f64,
+    rows: usize,
+) -> Result<Option<ColumnOutliers>> {
+    let cast = col.cast(&DataType::Float64)?;
+    let indexed: Vec<(usize, f64)> = cast
+        .f64()?
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, value)| value.filter(|v| !v.is_nan()).map(|v| (idx, v)))
+        .collect();
+
+    let mut sorted: Vec<f64> = indexed.iter().map(|(_, v)| *v).collect();
+    sorted.sort_by(f64::total_cmp);
+    // Indicators and other two-valued columns have no outliers, only a rarer
+    // value.
+    if sorted.windows(2).filter(|pair| pair[0] != pair[1]).count() < 2 {
+        return Ok(None);
+    }
+
+    let Some((lower, upper)) = bounds(&sorted, method, threshold) else {
+        return Ok(None);
+    };
+
+    let outside: Vec<usize> = indexed
+        .iter()
+        .filter(|(_, v)| *v < lower || *v > upper)
+        .map(|(idx, _)| *idx)
+        .collect();
+
+    Ok(Some(ColumnOutliers {
+        column: col.name().to_string(),
+        lower,
+        upper,
+        count: outside.len(),
+        percentage: percentage(outside.len(), rows),
+        examples: outside.into_iter().take(MAX_EXAMPLES).collect(),
+    }))
+}
+
+/// Every method boils down to a closed interval of accepted values. `None`
+/// when the spread is zero: the interval would collapse onto the centre and
+/// flag every value that differs from it.
+fn bounds(sorted: &[f64], method: OutlierMethod, k: f64) -> Option<(f64, f64)> {
+    match method {
+        OutlierMethod::Iqr => {
+            let q1 = stats::quantile(sorted, 0.25)?;
+            let q3 = stats::quantile(sorted, 0.75)?;
+            let iqr = q3 - q1;
+            if iqr == 0.0 {
+                return None;
+            }
+            Some((q1 - k * iqr, q3 + k * iqr))
+        }
+        OutlierMethod::Zscore => {
+            let mean = stats::mean(sorted)?;
+            let std = stats::std_dev(sorted).filter(|std| *std > 0.0)?;
+            Some((mean - k * std, mean + k * std))
+        }
+        OutlierMethod::Mad => {
+            let median = stats::quantile(sorted, 0.5)?;
+            let mut deviations: Vec<f64> = sorted.iter().map(|v| (v - median).abs()).collect();
+            deviations.sort_by(f64::total_cmp);
+            let mad = stats::quantile(&deviations, 0.5).filter(|mad| *mad > 0.0)?;
+            let spread = k * mad / MAD_SCALE;
+            Some((median - spread, median + spread))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mostly 5, with a small and a large value, and an indicator that is
+    /// mostly 0.
+    fn frame() -> DataFrame {
+        let mut spiky = vec![5.0; 97];
+        spiky.extend([1.0, 2.0, 100.0]);
+        let flag: Vec<i64> = (0..100).map(|i| i64::from(i % 20 == 0)).collect();
+        df!("spiky" => spiky, "flag" => flag).unwrap()
+    }
+
+    fn flagged(method: OutlierMethod) -> Vec<String> {
+        detect(&frame(), method, method.default_threshold())
+            .unwrap()
+            .columns
+            .into_iter()
+            .map(|c| c.column)
+            .collect()
+    }
+
+    #[test]
+    fn iqr_skips_zero_spread_and_indicators() {
+        assert!(flagged(OutlierMethod::Iqr).is_empty());
+    }
+
+    #[test]
+    fn mad_skips_zero_spread_and_indicators() {
+        assert!(flagged(OutlierMethod::Mad).is_empty());
+    }
+
+    #[test]
+    fn zscore_skips_indicators() {
+        let outliers = detect(&frame(), OutlierMethod::Zscore, 3.0).unwrap();
+        assert_eq!(outliers.columns.len(), 1);
+        assert_eq!(outliers.columns[0].column, "spiky");
+        assert_eq!(outliers.columns[0].examples, [99]);
+    }
+}
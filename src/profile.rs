@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use polars::prelude::*;
+use serde::Serialize;
+
+use crate::report::{format_quantiles, percentage};
+use crate::stats::{self, HistogramBin, Quantile};
+
+/// Equal-width bars in a numeric column's histogram.
+const HISTOGRAM_BINS: usize = 20;
+
+#[derive(Debug, Serialize)]
+pub struct ColumnProfile {
+    pub column: String,
+    pub dtype: String,
+    pub missing: usize,
+    pub missing_percentage: f64,
+    #[serde(flatten)]
+    pub stats: ProfileStats,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ProfileStats {
+    Numeric {
+        min: Option<f64>,
+        max: Option<f64>,
+        mean: Option<f64>,
+        std: Option<f64>,
+        quantiles: Vec<Quantile>,
+        histogram: Vec<HistogramBin>,
+    },
+    Text {
+        cardinality: usize,
+        empty_strings: usize,
+        top_values: Vec<ValueCount>,
+    },
+    Temporal {
+        min: Option<String>,
+        max: Option<String>,
+    },
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: usize,
+    pub percentage: f64,
+}
+
+pub fn profile_column(col: &Column, top_k: usize) -> Result<ColumnProfile> {
+    let dtype = col.dtype();
+    let stats = if dtype.is_primitive_numeric() {
+        numeric_stats(col)?
+    } else if dtype.is_temporal() {
+        temporal_stats(col)?
+    } else if dtype.is_string() || dtype.is_categorical() || dtype.is_bool() {
+        text_stats(col, top_k)?
+    } else {
+        ProfileStats::Other
+    };
+
+    Ok(ColumnProfile {
+        column: col.name().to_string(),
+        dtype: dtype.to_string(),
+        missing: col.null_count(),
+        missing_percentage: percentage(col.null_count(), col.len()),
+        stats,
+    })
+}
+
+fn numeric_stats(col: &Column) -> Result<ProfileStats> {
+    let cast = col.cast(&DataType::Float64)?;
+    let mut values: Vec<f64> = cast
+        .f64()?
+        .into_iter()
+        .flatten()
+        .filter(|v| !v.is_nan())
+        .collect();
+    values.sort_by(f64::total_cmp);
+
+    Ok(ProfileStats::Numeric {
+        min: values.first().copied(),
+        max: values.last().copied(),
+        mean: stats::mean(&values),
+        std: stats::std_dev(&values),
+        quantiles: stats::summary_quantiles(&values),
+        histogram: stats::histogram(&values, HISTOGRAM_BINS),
+    })
+}
+
+fn temporal_stats(col: &Column) -> Result<ProfileStats> {
+    let bound = |scalar: Scalar| {
+        let value = scalar.value();
+        (!value.is_null()).then(|| value.to_string())
+    };
+
+    Ok(ProfileStats::Temporal {
+        min: bound(col.min_reduce()?),
+        max: bound(col.max_reduce()?),
+    })
+}
+
+fn text_stats(col: &Column, top_k: usize) -> Result<ProfileStats> {
+    let cast = col.cast(&DataType::String)?;
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut empty_strings = 0;
+
+    for value in cast.str()?.into_iter().flatten() {
+        if value.trim().is_empty() {
+            empty_strings += 1;
+        }
+        *counts.entry(value).or_default() += 1;
+    }
+
+    let cardinality = counts.len();
+    let mut top: Vec<(&str, usize)> = counts.into_iter().collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let top_values = top
+        .into_iter()
+        .take(top_k)
+        .map(|(value, count)| ValueCount {
+            value: value.to_string(),
+            count,
+            percentage: percentage(count, col.len()),
+        })
+        .collect();
+
+    Ok(ProfileStats::Text {
+        cardinality,
+        empty_strings,
+        top_values,
+    })
+}
+
+impl ColumnProfile {
+    pub fn print(&self) {
+        println!("├─ {} ({})", self.column, self.dtype);
+        println!(
+            "│  ├─ Missing: {} ({:.1}%)",
+            self.missing, self.missing_percentage
+        );
+
+        match &self.stats {
+            ProfileStats::Numeric {
+                min,
+                max,
+                mean,
+                std,
+                quantiles,
+                ..
+            } => {
+                let fmt = |v: &Option<f64>| v.map_or("-".to_string(), |v| format!("{:.3}", v));
+                println!("│  ├─ Range: [{}, {}]", fmt(min), fmt(max));
+                println!("│  ├─ Mean: {} (std {})", fmt(mean), fmt(std));
+                println!("│  └─ Quantiles: {}", format_quantiles(quantiles));
+            }
+            ProfileStats::Text {
+                cardinality,
+                empty_strings,
+                top_values,
+            } => {
+                println!("│  ├─ Cardinality: {}", cardinality);
+                println!("│  ├─ Empty strings: {}", empty_strings);
+                let top = top_values
+                    .iter()
+                    .map(|v| format!("{} ({}, {:.1}%)", v.value, v.count, v.percentage))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("│  └─ Top values: {}", top);
+            }
+            ProfileStats::Temporal { min, max } => {
+                println!(
+                    "│  └─ Range: {} → {}",
+                    min.as_deref().unwrap_or("-"),
+                    max.as_deref().unwrap_or("-")
+                );
+            }
+            ProfileStats::Other => println!("│  └─ No profile for this dtype"),
+        }
+    }
+}
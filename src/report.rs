@@ -0,0 +1,680 @@
+use std::fmt;
+
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::check::CheckKind;
+use crate::contract::ContractReport;
+use crate::html::{self, MissingMap};
+use crate::identifiers::Identifiers;
+use crate::junit;
+use crate::markdown;
+use crate::outliers::Outliers;
+use crate::profile::ColumnProfile;
+use crate::sample::SampleInfo;
+use crate::stats::Quantile;
+use crate::target::{ClassBalance, TargetDistribution, TargetReport};
+
+/// Bumped whenever a field is renamed, removed or changes meaning. Adding new
+/// fields does not require a bump.
+pub const REPORT_SCHEMA_VERSION: u32 = 2;
+
+/// Classes beyond this are summarised in text output; JSON lists them all.
+pub const MAX_CLASSES_SHOWN: usize = 20;
+
+/// Formats of the validation report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Text,
+    Json,
+    /// Self-contained page with charts, for sharing outside the terminal
+    Html,
+    /// GitHub-flavoured Markdown, e.g. for a pull request comment
+    Markdown,
+    /// JUnit XML with one test case per check, for CI test reporting
+    Junit,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Html => write!(f, "html"),
+            OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::Junit => write!(f, "junit"),
+        }
+    }
+}
+
+/// Formats of the inspect report; markdown and junit summarize findings,
+/// which inspect does not raise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InspectFormat {
+    Text,
+    Json,
+    /// Self-contained page with charts, for sharing outside the terminal
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warn => write!(f, "warn"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single issue raised by a check, e.g. missing values in one column.
+#[derive(Debug, Serialize)]
+pub struct Finding {
+    pub check: String,
+    pub column: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    pub schema_version: u32,
+    pub file: String,
+    pub warnings: Vec<String>,
+    pub overview: Overview,
+    pub execution: Execution,
+    /// Present when the checks ran on a random sample; counts and percentages
+    /// then describe the sample and are estimates for the full file.
+    pub sample: Option<SampleInfo>,
+    /// Checks turned off with `--checks` or the config file; their sections
+    /// are absent.
+    pub disabled: Vec<CheckKind>,
+    /// Enabled checks that cannot run in streaming mode; their sections are
+    /// absent.
+    pub skipped: Vec<CheckKind>,
+    /// Columns that were checked, i.e. without ignored ones.
+    #[serde(skip)]
+    pub columns: Vec<String>,
+    pub missing: Option<Vec<MissingValues>>,
+    pub duplicates: Option<Duplicates>,
+    /// Absent in streaming mode, where outlier detection is skipped.
+    pub outliers: Option<Outliers>,
+    pub constant: Option<ConstantColumns>,
+    pub identifiers: Option<Identifiers>,
+    pub target: Option<TargetReport>,
+    pub contract: Option<ContractReport>,
+    pub findings: Vec<Finding>,
+    pub fail_on: Severity,
+    pub passed: bool,
+    /// Column profiles and the missing-value map drawn in HTML reports; only
+    /// collected for that format.
+    #[serde(skip)]
+    pub profiles: Vec<ColumnProfile>,
+    #[serde(skip)]
+    pub missing_map: Option<MissingMap>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectReport {
+    pub schema_version: u32,
+    pub file: String,
+    pub format: String,
+    pub warnings: Vec<String>,
+    pub overview: Overview,
+    pub sample: Option<SampleInfo>,
+    pub columns: Vec<ColumnProfile>,
+    #[serde(skip)]
+    pub missing_map: Option<MissingMap>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Overview {
+    pub rows: usize,
+    pub columns: usize,
+    /// Estimated in-memory size, or the file size in streaming mode.
+    pub size_bytes: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Execution {
+    pub streaming: bool,
+    /// `--memory-limit`; the CLI stops a run that allocates past it.
+    pub memory_limit_bytes: Option<u64>,
+    /// Rows per streaming morsel, when set for this process.
+    pub morsel_rows: Option<usize>,
+    /// Disk used for decompressed copies of compressed input.
+    pub spilled: Option<Spilled>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Spilled {
+    pub dir: String,
+    pub bytes: u64,
+}
+
+impl fmt::Display for Spilled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "decompressed {:.1} MiB into {}, deleted when the run ends",
+            self.bytes as f64 / (1u64 << 20) as f64,
+            self.dir
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissingValues {
+    pub column: String,
+    pub count: usize,
+    pub percentage: f64,
+    /// 95% interval for the full file's percentage, when sampled.
+    pub ci: Option<ConfidenceInterval>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Duplicates {
+    pub count: usize,
+    /// When sampled, the share of sampled rows repeating another sampled row.
+    pub percentage: f64,
+    /// Measured on a sample. A duplicate is only seen when both copies are
+    /// drawn, so `count` is a lower bound for the file and `percentage`
+    /// understates its rate; no interval is given for it.
+    pub sampled: bool,
+}
+
+impl fmt::Display for Duplicates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sampled {
+            write!(
+                f,
+                "at least {} duplicate rows ({:.1}% of the sample, a low estimate for the file)",
+                self.count, self.percentage
+            )
+        } else {
+            write!(f, "{} duplicate rows ({:.1}%)", self.count, self.percentage)
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConstantColumns {
+    pub max_dominant_pct: f64,
+    /// Only the flagged columns.
+    pub columns: Vec<ConstantColumn>,
+}
+
+/// A column whose most frequent value fills every row, or more than
+/// `max_dominant_pct` of them.
+#[derive(Debug, Serialize)]
+pub struct ConstantColumn {
+    pub column: String,
+    /// The most frequent value; `None` when that is null.
+    pub value: Option<String>,
+    pub count: usize,
+    pub percentage: f64,
+    /// Distinct values, counting null as one.
+    pub unique: usize,
+}
+
+impl fmt::Display for ConstantColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match &self.value {
+            Some(value) => format!("'{}'", value),
+            None => "null".to_string(),
+        };
+        if self.unique == 1 {
+            write!(f, "constant, every row is {}", value)
+        } else {
+            write!(
+                f,
+                "near-constant, {} in {} rows ({:.1}%)",
+                value, self.count, self.percentage
+            )
+        }
+    }
+}
+
+/// Bounds of a percentage estimated from a sample.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ConfidenceInterval {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl fmt::Display for ConfidenceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "95% CI {:.1}–{:.1}%", self.low, self.high)
+    }
+}
+
+/// A percentage with its confidence interval when estimated from a sample.
+pub fn format_percentage(percentage: f64, ci: Option<&ConfidenceInterval>) -> String {
+    match ci {
+        Some(ci) => format!("{:.1}% ({})", percentage, ci),
+        None => format!("{:.1}%", percentage),
+    }
+}
+
+pub fn percentage(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (count as f64 / total as f64) * 100.0
+}
+
+/// Writes a rendered report to `output`, or to stdout when no path is given.
+pub fn write_output(contents: &str, output: Option<&str>) -> Result<()> {
+    match output {
+        Some(path) => {
+            std::fs::write(path, contents)?;
+            eprintln!("📄 Wrote report to {}", path);
+        }
+        None => println!("{}", contents),
+    }
+    Ok(())
+}
+
+impl InspectReport {
+    /// Text output is printed by the inspect command as it goes; this covers
+    /// the rendered formats.
+    pub fn write(&self, format: InspectFormat, output: Option<&str>) -> Result<()> {
+        match format {
+            InspectFormat::Text => bail!("inspect prints text output directly"),
+            InspectFormat::Json => write_output(&serde_json::to_string_pretty(self)?, output),
+            InspectFormat::Html => write_output(&html::inspect_page(self), output),
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Why a check's results are absent: it was disabled or cannot run in
+    /// streaming mode.
+    pub fn skip_reason(&self, check: CheckKind) -> &'static str {
+        if self.disabled.contains(&check) {
+            "Disabled"
+        } else {
+            "Skipped in streaming mode"
+        }
+    }
+
+    pub fn print(&self, format: OutputFormat, output: Option<&str>) -> Result<()> {
+        match format {
+            OutputFormat::Text => self.print_text(),
+            OutputFormat::Json => write_output(&serde_json::to_string_pretty(self)?, output)?,
+            OutputFormat::Html => write_output(&html::validation_page(self), output)?,
+            OutputFormat::Markdown => write_output(&markdown::validation_markdown(self), output)?,
+            OutputFormat::Junit => write_output(&junit::validation_junit(self), output)?,
+        }
+        Ok(())
+    }
+
+    fn print_text(&self) {
+        println!("✓ Validating: {}\n", self.file);
+        print_load_warnings(&self.warnings);
+
+        println!("📊 Dataset Overview");
+        println!(
+            "├─ Shape: {} rows × {} columns",
+            self.overview.rows, self.overview.columns
+        );
+        if self.execution.streaming {
+            println!(
+                "├─ Size on disk: {:.2} MB",
+                self.overview.size_bytes as f64 / 1_000_000.0
+            );
+            if let Some(spilled) = &self.execution.spilled {
+                println!("├─ Disk: {}", spilled);
+            }
+            match (
+                self.execution.memory_limit_bytes,
+                self.execution.morsel_rows,
+            ) {
+                (Some(limit), Some(morsel_rows)) => println!(
+                    "└─ Mode: streaming ({:.0} MiB limit, {} rows per morsel)\n",
+                    limit as f64 / (1u64 << 20) as f64,
+                    morsel_rows
+                ),
+                (Some(limit), None) => println!(
+                    "└─ Mode: streaming ({:.0} MiB limit)\n",
+                    limit as f64 / (1u64 << 20) as f64
+                ),
+                _ => println!("└─ Mode: streaming\n"),
+            }
+        } else if let Some(sample) = &self.sample {
+            println!(
+                "├─ Size: {:.2} MB",
+                self.overview.size_bytes as f64 / 1_000_000.0
+            );
+            println!("└─ {}\n", sample);
+        } else {
+            println!(
+                "└─ Size: {:.2} MB\n",
+                self.overview.size_bytes as f64 / 1_000_000.0
+            );
+        }
+
+        println!("🔍 Missing Values:");
+        match &self.missing {
+            Some(missing) => print_missing(missing),
+            None => println!("└─ {}", self.skip_reason(CheckKind::Missing)),
+        }
+
+        println!("\n🔁 Duplicates:");
+        match &self.duplicates {
+            Some(duplicates) if duplicates.count > 0 => println!("└─ ⚠️  {}", duplicates),
+            Some(_) => println!("└─ ✓ No duplicates"),
+            None => println!("└─ {}", self.skip_reason(CheckKind::Duplicates)),
+        }
+
+        match &self.outliers {
+            Some(outliers) => print_outliers(outliers),
+            None => println!(
+                "\n📏 Outliers:\n└─ {}",
+                self.skip_reason(CheckKind::Outliers)
+            ),
+        }
+
+        match &self.constant {
+            Some(constant) => print_constant(constant),
+            None => println!(
+                "\n🧊 Constant Columns:\n└─ {}",
+                self.skip_reason(CheckKind::Constant)
+            ),
+        }
+
+        match &self.identifiers {
+            Some(identifiers) => print_identifiers(identifiers),
+            None => println!(
+                "\n🆔 ID-like Columns:\n└─ {}",
+                self.skip_reason(CheckKind::Identifiers)
+            ),
+        }
+
+        if let Some(target) = &self.target {
+            print_target(target);
+            print_target_leakage(target);
+        }
+
+        if let Some(contract) = &self.contract {
+            print_contract(contract);
+        }
+
+        self.print_findings();
+    }
+
+    fn print_findings(&self) {
+        println!("\n🚦 Findings:");
+        for finding in &self.findings {
+            let icon = match finding.severity {
+                Severity::Info => "ℹ️ ",
+                Severity::Warn => "⚠️ ",
+                Severity::Error => "❌",
+            };
+            match &finding.column {
+                Some(column) => println!(
+                    "├─ {} [{}] {}: {}",
+                    icon, finding.check, column, finding.message
+                ),
+                None => println!("├─ {} [{}] {}", icon, finding.check, finding.message),
+            }
+        }
+
+        if self.passed {
+            println!("└─ ✓ Passed (fail-on: {})", self.fail_on);
+        } else {
+            println!("└─ ❌ Failed (fail-on: {})", self.fail_on);
+        }
+    }
+}
+
+fn print_missing(missing: &[MissingValues]) {
+    let mut has_missing = false;
+
+    for missing in missing.iter().filter(|m| m.count > 0) {
+        has_missing = true;
+        match &missing.ci {
+            Some(ci) => println!(
+                "├─ {}: {} ({:.1}%, {})",
+                missing.column, missing.count, missing.percentage, ci
+            ),
+            None => println!(
+                "├─ {}: {} ({:.1}%)",
+                missing.column, missing.count, missing.percentage
+            ),
+        }
+    }
+
+    if !has_missing {
+        println!("└─ ✓ No missing values");
+    }
+}
+
+fn print_outliers(outliers: &Outliers) {
+    println!(
+        "\n📏 Outliers ({}, k={}):",
+        outliers.method, outliers.threshold
+    );
+
+    let flagged: Vec<_> = outliers.columns.iter().filter(|c| c.count > 0).collect();
+    if flagged.is_empty() {
+        println!("└─ ✓ No outliers");
+        return;
+    }
+
+    for col in flagged {
+        let examples = col
+            .examples
+            .iter()
+            .map(|idx| idx.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "├─ {}: {} ({:.1}%) outside [{:.3}, {:.3}], e.g. rows {}",
+            col.column, col.count, col.percentage, col.lower, col.upper, examples
+        );
+    }
+}
+
+fn print_constant(constant: &ConstantColumns) {
+    println!(
+        "\n🧊 Constant Columns (dominant value > {}%):",
+        constant.max_dominant_pct
+    );
+
+    if constant.columns.is_empty() {
+        println!("└─ ✓ No constant columns");
+        return;
+    }
+    for column in &constant.columns {
+        println!("├─ {}: {}", column.column, column);
+    }
+}
+
+fn print_identifiers(identifiers: &Identifiers) {
+    println!(
+        "\n🆔 ID-like Columns (distinct values > {}%):",
+        identifiers.max_unique_pct
+    );
+
+    if identifiers.columns.is_empty() {
+        println!("└─ ✓ No ID-like columns");
+        return;
+    }
+    for column in &identifiers.columns {
+        println!(
+            "├─ {}: {}, {} distinct values ({:.1}%)",
+            column.column, column.kind, column.unique, column.percentage
+        );
+    }
+}
+
+fn print_target(target: &TargetReport) {
+    println!("\n🎯 Target Column: {}", target.column);
+
+    if !target.found {
+        println!("└─ ❌ Target column '{}' not found!", target.column);
+        return;
+    }
+
+    println!("├─ Type: {}", target.dtype.as_deref().unwrap_or("?"));
+    println!("├─ Unique values: {}", target.unique.unwrap_or(0));
+
+    match target.missing {
+        Some(missing) if missing > 0 => println!(
+            "├─ ⚠️  Missing in target: {} ({:.1}%)",
+            missing,
+            target.missing_percentage.unwrap_or(0.0)
+        ),
+        _ => println!("├─ ✓ No missing values in target"),
+    }
+
+    if let Some(classes) = &target.classes {
+        print_class_balance(classes);
+    } else if let Some(dist) = &target.distribution {
+        print_target_distribution(dist);
+    } else {
+        println!("└─ Task: regression (non-numeric target, no distribution)");
+    }
+}
+
+fn print_target_leakage(target: &TargetReport) {
+    let Some(suspects) = &target.leakage else {
+        return;
+    };
+
+    println!("\n🕵️  Target Leakage:");
+    if suspects.is_empty() {
+        println!("└─ ✓ No feature gives the target away");
+        return;
+    }
+    for suspect in suspects {
+        let icon = if suspect.rule == "name" {
+            "⚠️ "
+        } else {
+            "❌"
+        };
+        println!(
+            "├─ {} {} [{}]: {}",
+            icon, suspect.column, suspect.rule, suspect.message
+        );
+    }
+    println!("└─ {} suspected leaks", suspects.len());
+}
+
+fn print_contract(contract: &ContractReport) {
+    println!("\n📜 Schema Contract: {}", contract.contract);
+
+    if contract.violations.is_empty() {
+        println!(
+            "└─ ✓ All {} declared columns match the contract",
+            contract.columns_checked
+        );
+        return;
+    }
+
+    for violation in &contract.violations {
+        if violation.examples.is_empty() {
+            println!(
+                "├─ ❌ {} [{}]: {}",
+                violation.column, violation.rule, violation.message
+            );
+        } else {
+            println!(
+                "├─ ❌ {} [{}]: {}, e.g. {}",
+                violation.column,
+                violation.rule,
+                violation.message,
+                violation.examples.join(", ")
+            );
+        }
+    }
+    println!("└─ {} violations", contract.violations.len());
+}
+
+fn print_class_balance(classes: &ClassBalance) {
+    println!("├─ Task: classification");
+    println!("├─ Classes:");
+    let shown = classes.counts.len().min(MAX_CLASSES_SHOWN);
+    for (i, class) in classes.counts.iter().take(shown).enumerate() {
+        let branch = if i + 1 == shown && shown == classes.counts.len() {
+            "└─"
+        } else {
+            "├─"
+        };
+        println!(
+            "│  {} {}: {} ({:.1}%)",
+            branch, class.class, class.count, class.percentage
+        );
+    }
+    if shown < classes.counts.len() {
+        println!("│  └─ … {} more", classes.counts.len() - shown);
+    }
+
+    println!("├─ Imbalance ratio: {:.2}", classes.imbalance_ratio);
+
+    if classes.rare_classes.is_empty() {
+        println!(
+            "├─ ✓ Every class has at least {} rows",
+            classes.min_class_count
+        );
+    } else {
+        println!(
+            "├─ ⚠️  Classes with fewer than {} rows: {}",
+            classes.min_class_count,
+            classes.rare_classes.join(", ")
+        );
+    }
+
+    if classes.empty_in_split.is_empty() {
+        println!(
+            "└─ ✓ Every class survives a {:.0}% stratified split",
+            classes.test_size * 100.0
+        );
+    } else {
+        println!(
+            "└─ ⚠️  Empty after a {:.0}% stratified split: {}",
+            classes.test_size * 100.0,
+            classes.empty_in_split.join(", ")
+        );
+    }
+}
+
+fn print_target_distribution(dist: &TargetDistribution) {
+    println!("├─ Task: regression");
+    println!("├─ Range: [{:.3}, {:.3}]", dist.min, dist.max);
+    println!("├─ Mean: {:.3} (std {:.3})", dist.mean, dist.std);
+    println!("├─ Skewness: {:.3}", dist.skewness);
+    println!("└─ Quantiles: {}", format_quantiles(&dist.quantiles));
+}
+
+pub fn format_quantiles(quantiles: &[Quantile]) -> String {
+    quantiles
+        .iter()
+        .map(|q| format!("p{:.0}={:.3}", q.q * 100.0, q.value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn print_load_warnings(warnings: &[String]) {
+    if warnings.is_empty() {
+        return;
+    }
+
+    println!("⚠️  Schema Warnings:");
+    for warning in warnings {
+        println!("├─ {}", warning);
+    }
+    println!();
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+
+use anyhow::Result;
+use polars::prelude::*;
+use serde::Serialize;
+
+use crate::report::ConfidenceInterval;
+use crate::stats;
+use crate::streaming::collect_streaming;
+
+const ROW_ALIAS: &str = "__mlcheck_row";
+
+#[derive(Debug, Clone, Copy)]
+pub enum SampleSize {
+    Rows(usize),
+    Fraction(f64),
+}
+
+#[derive(Debug, Serialize)]
+pub struct SampleInfo {
+    pub rows: usize,
+    pub population_rows: usize,
+    pub fraction: f64,
+    pub seed: u64,
+}
+
+/// Which rows of a dataset were drawn into a sample.
+pub struct Sample {
+    pub info: SampleInfo,
+    /// Row index in the full dataset of every sampled row.
+    pub source_rows: Vec<usize>,
+}
+
+/// Parses a sampling fraction in `(0, 1]`.
+pub fn parse_fraction(input: &str) -> Result<f64, String> {
+    let fraction: f64 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid fraction '{}'", input))?;
+    if fraction <= 0.0 || fraction > 1.0 {
+        return Err(format!("fraction must be in (0, 1], got {}", fraction));
+    }
+    Ok(fraction)
+}
+
+/// Draws rows without replacement. The same `seed` always selects the same
+/// rows of the same file, so a quick check can be reproduced exactly. The
+/// sampled frame keeps the original row order.
+pub fn sample(df: &DataFrame, size: SampleSize, seed: u64) -> Result<(DataFrame, Sample)> {
+    let (picked, sample) = pick(df.height(), size, seed)?;
+    Ok((df.take(&picked)?, sample))
+}
+
+/// Lazy counterpart of [`sample`] that picks the same rows. The scan is
+/// counted first, then joined against the picked row numbers on the
+/// streaming engine, so only the sampled rows are materialized.
+pub fn sample_lazy(lf: LazyFrame, size: SampleSize, seed: u64) -> Result<(DataFrame, Sample)> {
+    let counted = collect_streaming(lf.clone().select([len().alias(ROW_ALIAS)]))?;
+    let population_rows = counted.column(ROW_ALIAS)?.cast(&DataType::UInt64)?;
+    let population_rows = population_rows.u64()?.get(0).unwrap_or(0) as usize;
+
+    let (picked, sample) = pick(population_rows, size, seed)?;
+    let picked = DataFrame::new(vec![picked.with_name(ROW_ALIAS.into()).into_column()])?;
+    let sampled = collect_streaming(lf.with_row_index(ROW_ALIAS, None).join(
+        picked.lazy(),
+        [col(ROW_ALIAS)],
+        [col(ROW_ALIAS)],
+        JoinArgs::new(JoinType::Inner),
+    ))?;
+    let sampled = sampled
+        .sort([ROW_ALIAS], SortMultipleOptions::default())?
+        .drop(ROW_ALIAS)?;
+    Ok((sampled, sample))
+}
+
+/// Picks the sorted row numbers to draw out of `population_rows`.
+fn pick(population_rows: usize, size: SampleSize, seed: u64) -> Result<(IdxCa, Sample)> {
+    let rows = match size {
+        SampleSize::Rows(n) => n,
+        SampleSize::Fraction(fraction) => (population_rows as f64 * fraction).round() as usize,
+    }
+    .min(population_rows);
+
+    let all: IdxCa = (0..population_rows as IdxSize).collect_ca(PlSmallStr::EMPTY);
+    let picked = all.sample_n(rows, false, false, Some(seed))?.sort(false);
+
+    let sample = Sample {
+        info: SampleInfo {
+            rows,
+            population_rows,
+            fraction: if population_rows == 0 {
+                1.0
+            } else {
+                rows as f64 / population_rows as f64
+            },
+            seed,
+        },
+        source_rows: picked.into_no_null_iter().map(|i| i as usize).collect(),
+    };
+    Ok((picked, sample))
+}
+
+impl SampleInfo {
+    /// 95% interval, in percent, for a share of rows measured on the sample.
+    pub fn interval(&self, count: usize) -> ConfidenceInterval {
+        let (low, high) = stats::wilson_interval(count, self.rows, self.population_rows);
+        ConfidenceInterval {
+            low: low * 100.0,
+            high: high * 100.0,
+        }
+    }
+}
+
+impl fmt::Display for SampleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sample: {} of {} rows ({:.1}%, seed {}), results are estimates",
+            self.rows,
+            self.population_rows,
+            self.fraction * 100.0,
+            self.seed
+        )
+    }
+}
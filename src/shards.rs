@@ -0,0 +1,289 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use polars::prelude::*;
+
+use crate::compression;
+
+/// Hive's placeholder for a null partition value.
+const HIVE_NULL: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Extensions picked up when a directory is given, also when followed by a
+/// compression extension such as `.gz`.
+const DATA_EXTENSIONS: [&str; 7] = ["csv", "tsv", "txt", "parquet", "pq", "jsonl", "ndjson"];
+
+/// One file of a dataset split over a directory tree or glob.
+pub struct Shard {
+    pub path: String,
+    /// `key=value` directories between the dataset root and the file.
+    pub partitions: Vec<(String, Option<String>)>,
+}
+
+pub struct Shards {
+    pub files: Vec<Shard>,
+}
+
+/// Expands a directory or glob into its data files, sorted by path. Returns
+/// `None` for a plain file path, which is read as before.
+///
+/// Directories are searched recursively for files with a known data
+/// extension; hidden files and markers such as `_SUCCESS` are skipped.
+pub fn discover(path: &str) -> Result<Option<Shards>> {
+    let (root, mut files) = if Path::new(path).is_dir() {
+        let mut files = Vec::new();
+        walk(Path::new(path), &mut files)?;
+        (PathBuf::from(path), files)
+    } else if is_glob(path) {
+        let files = glob::glob(path)
+            .with_context(|| format!("invalid glob pattern {}", path))?
+            .filter_map(|entry| entry.ok())
+            .filter(|file| file.is_file())
+            .collect();
+        (glob_root(path), files)
+    } else {
+        return Ok(None);
+    };
+
+    if files.is_empty() {
+        bail!("no data files found in {}", path);
+    }
+    files.sort();
+
+    let files = files
+        .into_iter()
+        .map(|file| {
+            let relative = file.strip_prefix(&root).unwrap_or(&file);
+            Shard {
+                partitions: hive_partitions(relative),
+                path: file.to_string_lossy().into_owned(),
+            }
+        })
+        .collect();
+    Ok(Some(Shards { files }))
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// The directory a glob starts matching in, e.g. `data` for
+/// `data/date=*/part-*.csv`.
+fn glob_root(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|c| !is_glob(&c.as_os_str().to_string_lossy()))
+        .collect()
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name.starts_with('.') || name.starts_with('_') {
+            continue;
+        }
+        if path.is_dir() {
+            walk(&path, files)?;
+        } else if compression::strip_extension(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| DATA_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// `key=value` directory names of a path relative to the dataset root.
+fn hive_partitions(relative: &Path) -> Vec<(String, Option<String>)> {
+    let Some(parent) = relative.parent() else {
+        return Vec::new();
+    };
+    parent
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                let (key, value) = name.split_once('=')?;
+                let value = (value != HIVE_NULL).then(|| value.to_string());
+                Some((key.to_string(), value))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+impl Shards {
+    /// Describes every shard whose columns or dtypes differ from the first
+    /// shard's. `schemas` holds each shard's schema, in order.
+    pub fn schema_mismatches(&self, schemas: &[Schema]) -> Vec<String> {
+        let (Some(first), Some(reference)) = (self.files.first(), schemas.first()) else {
+            return Vec::new();
+        };
+
+        let mut warnings = Vec::new();
+        for (shard, schema) in self.files.iter().zip(schemas).skip(1) {
+            for (name, dtype) in schema.iter() {
+                match reference.get(name) {
+                    None => warnings.push(format!(
+                        "{}: extra column '{}' not in {}",
+                        shard.path, name, first.path
+                    )),
+                    Some(expected) if expected != dtype => warnings.push(format!(
+                        "{}: column '{}' is {}, but {} in {}",
+                        shard.path, name, dtype, expected, first.path
+                    )),
+                    Some(_) => {}
+                }
+            }
+            for name in reference.iter_names() {
+                if !schema.contains(name) {
+                    warnings.push(format!(
+                        "{}: missing column '{}' present in {}",
+                        shard.path, name, first.path
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Adds the partition columns to each shard and stacks them. Columns a
+    /// shard lacks are filled with nulls and differing dtypes are widened to a
+    /// common supertype.
+    pub fn combine(&self, frames: Vec<LazyFrame>, schemas: &[Schema]) -> Result<LazyFrame> {
+        let mut keys: Vec<&str> = Vec::new();
+        for shard in &self.files {
+            for (key, _) in &shard.partitions {
+                if !keys.contains(&key.as_str()) {
+                    keys.push(key);
+                }
+            }
+        }
+        let dtypes: Vec<DataType> = keys.iter().map(|key| self.partition_dtype(key)).collect();
+
+        let frames: Vec<LazyFrame> = frames
+            .into_iter()
+            .zip(&self.files)
+            .zip(schemas)
+            .map(|((lf, shard), schema)| {
+                let columns: Vec<Expr> = keys
+                    .iter()
+                    .zip(&dtypes)
+                    // A file that stores the partition column itself wins.
+                    .filter(|(key, _)| !schema.contains(key))
+                    .map(|(&key, dtype)| {
+                        let value = shard
+                            .partitions
+                            .iter()
+                            .find(|(k, _)| k == key)
+                            .and_then(|(_, v)| v.clone());
+                        let value = match value {
+                            Some(v) => lit(v),
+                            None => lit(NULL),
+                        };
+                        value.cast(dtype.clone()).alias(key)
+                    })
+                    .collect();
+                lf.with_columns(columns)
+            })
+            .collect();
+
+        let args = UnionArgs {
+            to_supertypes: true,
+            ..Default::default()
+        };
+        Ok(concat_lf_diagonal(frames, args)?)
+    }
+
+    /// Integer or float when every value of the partition key parses as one,
+    /// otherwise text.
+    fn partition_dtype(&self, key: &str) -> DataType {
+        let values: Vec<&str> = self
+            .files
+            .iter()
+            .flat_map(|shard| &shard.partitions)
+            .filter(|(k, _)| k == key)
+            .filter_map(|(_, v)| v.as_deref())
+            .collect();
+
+        if values.is_empty() {
+            DataType::String
+        } else if values.iter().all(|v| v.parse::<i64>().is_ok()) {
+            DataType::Int64
+        } else if values.iter().all(|v| v.parse::<f64>().is_ok()) {
+            DataType::Float64
+        } else {
+            DataType::String
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shards(paths: &[&str]) -> Shards {
+        Shards {
+            files: paths
+                .iter()
+                .map(|path| Shard {
+                    path: path.to_string(),
+                    partitions: hive_partitions(Path::new(path)),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn hive_directories_become_partitions() {
+        assert_eq!(
+            hive_partitions(Path::new(
+                "year=2026/region=__HIVE_DEFAULT_PARTITION__/misc/part-0.csv"
+            )),
+            [
+                ("year".to_string(), Some("2026".to_string())),
+                ("region".to_string(), None),
+            ]
+        );
+        assert!(hive_partitions(Path::new("part-0.csv")).is_empty());
+    }
+
+    #[test]
+    fn partition_dtype_follows_every_value() {
+        let shards = shards(&[
+            "n=1/f=0.5/s=a/p.csv",
+            "n=2/f=1/s=2/p.csv",
+            "n=__HIVE_DEFAULT_PARTITION__/f=2/s=b/p.csv",
+        ]);
+        assert_eq!(shards.partition_dtype("n"), DataType::Int64);
+        assert_eq!(shards.partition_dtype("f"), DataType::Float64);
+        assert_eq!(shards.partition_dtype("s"), DataType::String);
+        assert_eq!(shards.partition_dtype("absent"), DataType::String);
+    }
+
+    #[test]
+    fn schema_mismatches_name_extra_missing_and_retyped_columns() {
+        let shards = shards(&["a.csv", "b.csv"]);
+        let first = Schema::from_iter([
+            Field::new("id".into(), DataType::Int64),
+            Field::new("score".into(), DataType::Float64),
+        ]);
+        let second = Schema::from_iter([
+            Field::new("id".into(), DataType::String),
+            Field::new("note".into(), DataType::String),
+        ]);
+        assert_eq!(
+            shards.schema_mismatches(&[first.clone(), second]),
+            [
+                "b.csv: column 'id' is str, but i64 in a.csv",
+                "b.csv: extra column 'note' not in a.csv",
+                "b.csv: missing column 'score' present in a.csv",
+            ]
+        );
+        assert!(shards.schema_mismatches(&[first.clone(), first]).is_empty());
+    }
+}
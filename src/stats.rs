@@ -0,0 +1,539 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Quantiles reported in summaries, matching the usual describe() percentiles
+/// plus the tails.
+pub const SUMMARY_QUANTILES: [f64; 5] = [0.05, 0.25, 0.5, 0.75, 0.95];
+
+/// Standard normal quantile for a two-sided 95% interval.
+const Z_95: f64 = 1.959_964;
+
+#[derive(Debug, Serialize)]
+pub struct Quantile {
+    pub q: f64,
+    pub value: f64,
+}
+
+/// One bar of a histogram, covering `[lower, upper)`; the last bar also
+/// includes `upper`.
+#[derive(Debug, Serialize)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+}
+
+/// Two-sample Kolmogorov-Smirnov statistic: the largest distance between the
+/// empirical CDFs. Both slices must be sorted ascending.
+pub fn ks_statistic(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut max_diff: f64 = 0.0;
+
+    while i < a.len() && j < b.len() {
+        let x = a[i].min(b[j]);
+        while i < a.len() && a[i] <= x {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= x {
+            j += 1;
+        }
+        let diff = (i as f64 / a.len() as f64 - j as f64 / b.len() as f64).abs();
+        max_diff = max_diff.max(diff);
+    }
+
+    max_diff
+}
+
+/// Asymptotic p-value for the two-sample KS statistic.
+pub fn ks_p_value(d: f64, n1: usize, n2: usize) -> f64 {
+    if n1 == 0 || n2 == 0 {
+        return 1.0;
+    }
+
+    let ne = (n1 * n2) as f64 / (n1 + n2) as f64;
+    let lambda = (ne.sqrt() + 0.12 + 0.11 / ne.sqrt()) * d;
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+
+    let mut sum = 0.0;
+    for j in 1..=100 {
+        let j = j as f64;
+        let term = 2.0 * (-1f64).powf(j - 1.0) * (-2.0 * j * j * lambda * lambda).exp();
+        sum += term;
+        if term.abs() < 1e-10 {
+            break;
+        }
+    }
+    sum.clamp(0.0, 1.0)
+}
+
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample standard deviation (n - 1 denominator).
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values)?;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    Some(var.sqrt())
+}
+
+/// Sample skewness (Fisher-Pearson moment coefficient).
+pub fn skewness(values: &[f64]) -> Option<f64> {
+    let mean = mean(values)?;
+    let n = values.len() as f64;
+    let m2 = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let m3 = values.iter().map(|v| (v - mean).powi(3)).sum::<f64>() / n;
+    if m2 == 0.0 {
+        return Some(0.0);
+    }
+    Some(m3 / m2.powf(1.5))
+}
+
+/// Linear-interpolated quantile of a sorted slice, `q` in `[0, 1]`.
+pub fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    let frac = pos - lower as f64;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * frac)
+}
+
+/// Evaluates [`SUMMARY_QUANTILES`] on a sorted slice.
+pub fn summary_quantiles(sorted: &[f64]) -> Vec<Quantile> {
+    SUMMARY_QUANTILES
+        .iter()
+        .filter_map(|&q| quantile(sorted, q).map(|value| Quantile { q, value }))
+        .collect()
+}
+
+/// Equal-width histogram of a sorted slice. A constant column gets a single
+/// zero-width bin.
+pub fn histogram(sorted: &[f64], bins: usize) -> Vec<HistogramBin> {
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return Vec::new();
+    };
+    if min == max || bins == 0 {
+        return vec![HistogramBin {
+            lower: min,
+            upper: max,
+            count: sorted.len(),
+        }];
+    }
+
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0; bins];
+    for &value in sorted {
+        let bin = (((value - min) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| HistogramBin {
+            lower: min + width * i as f64,
+            upper: min + width * (i + 1) as f64,
+            count,
+        })
+        .collect()
+}
+
+/// Population Stability Index of `actual` against `expected`, binned on the
+/// quantiles of `expected`. Both slices must be sorted ascending.
+pub fn psi(expected: &[f64], actual: &[f64], bins: usize) -> f64 {
+    if expected.is_empty() || actual.is_empty() || bins == 0 {
+        return 0.0;
+    }
+
+    let mut edges: Vec<f64> = (1..bins)
+        .filter_map(|i| quantile(expected, i as f64 / bins as f64))
+        .collect();
+    edges.dedup();
+
+    let expected = bin_proportions(expected, &edges);
+    let actual = bin_proportions(actual, &edges);
+
+    expected
+        .iter()
+        .zip(&actual)
+        .map(|(&e, &a)| {
+            // Empty bins would make the log blow up, so floor them.
+            let (e, a) = (e.max(1e-4), a.max(1e-4));
+            (a - e) * (a / e).ln()
+        })
+        .sum()
+}
+
+fn bin_proportions(sorted: &[f64], edges: &[f64]) -> Vec<f64> {
+    let mut counts = vec![0usize; edges.len() + 1];
+    for &value in sorted {
+        let bin = edges.partition_point(|&edge| edge < value);
+        counts[bin] += 1;
+    }
+    counts
+        .into_iter()
+        .map(|c| c as f64 / sorted.len() as f64)
+        .collect()
+}
+
+/// Pearson chi-square statistic and degrees of freedom for a 2×k table of
+/// category counts.
+pub fn chi_square(a: &[usize], b: &[usize]) -> (f64, usize) {
+    let total_a: usize = a.iter().sum();
+    let total_b: usize = b.iter().sum();
+    let total = (total_a + total_b) as f64;
+    if total_a == 0 || total_b == 0 || a.len() < 2 {
+        return (0.0, 0);
+    }
+
+    let mut stat = 0.0;
+    for (&ca, &cb) in a.iter().zip(b) {
+        let col = (ca + cb) as f64;
+        for (observed, row) in [(ca, total_a), (cb, total_b)] {
+            let expected = row as f64 * col / total;
+            if expected > 0.0 {
+                stat += (observed as f64 - expected).powi(2) / expected;
+            }
+        }
+    }
+    (stat, a.len() - 1)
+}
+
+/// Upper-tail p-value of the chi-square distribution.
+pub fn chi_square_p_value(stat: f64, dof: usize) -> f64 {
+    if dof == 0 {
+        return 1.0;
+    }
+    gamma_q(dof as f64 / 2.0, stat / 2.0)
+}
+
+/// Jensen-Shannon divergence (base 2, so bounded by 1) between two count
+/// vectors over the same categories.
+pub fn js_divergence(a: &[usize], b: &[usize]) -> f64 {
+    let total_a: usize = a.iter().sum();
+    let total_b: usize = b.iter().sum();
+    if total_a == 0 || total_b == 0 {
+        return 0.0;
+    }
+
+    let mut divergence = 0.0;
+    for (&ca, &cb) in a.iter().zip(b) {
+        let p = ca as f64 / total_a as f64;
+        let q = cb as f64 / total_b as f64;
+        let m = (p + q) / 2.0;
+        if p > 0.0 {
+            divergence += 0.5 * p * (p / m).log2();
+        }
+        if q > 0.0 {
+            divergence += 0.5 * q * (q / m).log2();
+        }
+    }
+    divergence.clamp(0.0, 1.0)
+}
+
+/// Pearson correlation of paired values, or `None` when either side is
+/// constant.
+pub fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let mx = mean(x)?;
+    let my = mean(y)?;
+    let (mut cov, mut vx, mut vy) = (0.0, 0.0, 0.0);
+    for (&a, &b) in x.iter().zip(y) {
+        cov += (a - mx) * (b - my);
+        vx += (a - mx).powi(2);
+        vy += (b - my).powi(2);
+    }
+    if vx == 0.0 || vy == 0.0 {
+        return None;
+    }
+    Some(cov / (vx * vy).sqrt())
+}
+
+/// Shannon entropy in bits of a sequence of category codes.
+pub fn entropy(codes: &[usize]) -> f64 {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for &code in codes {
+        *counts.entry(code).or_default() += 1;
+    }
+    let n = codes.len() as f64;
+    counts
+        .values()
+        .map(|&c| {
+            let p = c as f64 / n;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Mutual information in bits between two paired sequences of category codes.
+pub fn mutual_information(a: &[usize], b: &[usize]) -> f64 {
+    let mut joint: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut count_a: HashMap<usize, usize> = HashMap::new();
+    let mut count_b: HashMap<usize, usize> = HashMap::new();
+    for (&x, &y) in a.iter().zip(b) {
+        *joint.entry((x, y)).or_default() += 1;
+        *count_a.entry(x).or_default() += 1;
+        *count_b.entry(y).or_default() += 1;
+    }
+
+    let n = a.len().min(b.len()) as f64;
+    joint
+        .iter()
+        .map(|(&(x, y), &c)| {
+            let pxy = c as f64 / n;
+            let px = count_a[&x] as f64 / n;
+            let py = count_b[&y] as f64 / n;
+            pxy * (pxy / (px * py)).log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+/// Two-sided 95% Wilson score interval for a proportion observed as
+/// `successes` out of `n` draws without replacement from `population` items.
+/// The finite population correction shrinks the interval as the sample
+/// approaches the whole population, down to a single point at `n == population`.
+pub fn wilson_interval(successes: usize, n: usize, population: usize) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    let p = successes as f64 / n as f64;
+    if n >= population {
+        return (p, p);
+    }
+
+    let fpc = (population - n) as f64 / (population as f64 - 1.0);
+    let z2 = Z_95 * Z_95 * fpc;
+    let n = n as f64;
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half = (z2 * (p * (1.0 - p) / n + z2 / (4.0 * n * n))).sqrt() / (1.0 + z2 / n);
+    ((centre - half).max(0.0), (centre + half).min(1.0))
+}
+
+/// Regularized upper incomplete gamma function Q(a, x).
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        1.0 - gamma_p_series(a, x)
+    } else {
+        gamma_q_continued_fraction(a, x)
+    }
+}
+
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut ap = a;
+    let mut sum = 1.0 / a;
+    let mut del = sum;
+    for _ in 0..500 {
+        ap += 1.0;
+        del *= x / ap;
+        sum += del;
+        if del.abs() < sum.abs() * 1e-12 {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn gamma_q_continued_fraction(a: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..500 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if (del - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+/// Lanczos approximation of ln Γ(x).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut ser = 1.000_000_000_190_015;
+    for (i, coeff) in COEFFS.iter().enumerate() {
+        ser += coeff / (x + 1.0 + i as f64);
+    }
+    -tmp + (2.506_628_274_631_000_5 * ser / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn ln_gamma_matches_factorials() {
+        assert_close(ln_gamma(1.0), 0.0, 1e-10);
+        assert_close(ln_gamma(2.0), 0.0, 1e-10);
+        assert_close(ln_gamma(5.0), 24f64.ln(), 1e-10);
+        assert_close(ln_gamma(11.0), 3_628_800f64.ln(), 1e-9);
+        // Γ(1/2) = √π
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-10);
+    }
+
+    #[test]
+    fn gamma_q_of_one_is_exponential() {
+        for x in [0.1, 1.0, 2.5, 10.0] {
+            assert_close(gamma_q(1.0, x), (-x).exp(), 1e-10);
+        }
+    }
+
+    #[test]
+    fn chi_square_survival_at_critical_values() {
+        // 5% critical values from standard chi-square tables.
+        assert_close(chi_square_p_value(3.841_458_820_694_124, 1), 0.05, 1e-8);
+        assert_close(chi_square_p_value(5.991_464_547_107_979, 2), 0.05, 1e-8);
+        assert_close(chi_square_p_value(18.307_038_053_275_146, 10), 0.05, 1e-8);
+        // 1% critical value, dof 5
+        assert_close(chi_square_p_value(15.086_272_469_388_99, 5), 0.01, 1e-8);
+        assert_close(chi_square_p_value(0.0, 3), 1.0, 1e-12);
+        assert_close(chi_square_p_value(12.0, 0), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn chi_square_of_two_by_two_table() {
+        // scipy.stats.chi2_contingency([[10, 20], [30, 40]], correction=False);
+        // the p-value is erfc(sqrt(stat / 2)) for one degree of freedom.
+        let (stat, dof) = chi_square(&[10, 20], &[30, 40]);
+        assert_close(stat, 0.793_650_793_650_793_6, 1e-12);
+        assert_eq!(dof, 1);
+        assert_close(chi_square_p_value(stat, dof), 0.372_998_483_613_487_1, 1e-8);
+    }
+
+    #[test]
+    fn ks_statistic_of_known_samples() {
+        assert_close(ks_statistic(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]), 0.0, 1e-12);
+        assert_close(ks_statistic(&[1.0, 2.0], &[3.0, 4.0]), 1.0, 1e-12);
+        assert_close(
+            ks_statistic(&[1.0, 2.0, 3.0, 4.0], &[3.0, 4.0, 5.0, 6.0]),
+            0.5,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn ks_p_value_matches_kolmogorov_distribution() {
+        // Q_KS(λ) at the tabulated 5% and 1% points and at λ = 1.
+        let n = 400;
+        let ne = (n * n) as f64 / (2 * n) as f64;
+        let scale = ne.sqrt() + 0.12 + 0.11 / ne.sqrt();
+        assert_close(ks_p_value(1.358_098_6 / scale, n, n), 0.05, 1e-6);
+        assert_close(ks_p_value(1.627_618_8 / scale, n, n), 0.01, 1e-6);
+        assert_close(ks_p_value(1.0 / scale, n, n), 0.269_999_671_677_355_3, 1e-8);
+        assert_close(ks_p_value(0.0, n, n), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn psi_of_shifted_distribution() {
+        let expected = [1.0, 2.0, 3.0, 4.0];
+        assert_close(psi(&expected, &expected, 2), 0.0, 1e-12);
+        // Bins split at 2.5: expected 50/50, actual 75/25.
+        let shifted = 0.25 * 1.5f64.ln() + 0.25 * 2f64.ln();
+        assert_close(psi(&expected, &[1.0, 1.0, 1.0, 4.0], 2), shifted, 1e-12);
+    }
+
+    #[test]
+    fn js_divergence_bounds_and_known_value() {
+        assert_close(js_divergence(&[3, 5], &[6, 10]), 0.0, 1e-12);
+        assert_close(js_divergence(&[1, 0], &[0, 1]), 1.0, 1e-12);
+        // P = (1/2, 1/2), Q = (1, 0)
+        assert_close(
+            js_divergence(&[1, 1], &[2, 0]),
+            0.311_278_124_459_132_8,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn pearson_of_known_pairs() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_close(
+            pearson(&x, &[2.0, 4.0, 6.0, 8.0, 10.0]).unwrap(),
+            1.0,
+            1e-12,
+        );
+        assert_close(
+            pearson(&x, &[5.0, 4.0, 3.0, 2.0, 1.0]).unwrap(),
+            -1.0,
+            1e-12,
+        );
+        assert_close(
+            pearson(&x, &[2.0, 4.0, 5.0, 4.0, 5.0]).unwrap(),
+            0.6f64.sqrt(),
+            1e-12,
+        );
+        assert!(pearson(&x, &[1.0; 5]).is_none());
+    }
+
+    #[test]
+    fn mutual_information_of_dependent_and_independent_codes() {
+        assert_close(mutual_information(&[0, 1, 0, 1], &[0, 1, 0, 1]), 1.0, 1e-12);
+        assert_close(mutual_information(&[0, 0, 1, 1], &[0, 1, 0, 1]), 0.0, 1e-12);
+        assert_close(entropy(&[0, 1, 2, 3]), 2.0, 1e-12);
+    }
+
+    #[test]
+    fn wilson_interval_matches_published_bounds() {
+        // 5 of 10 without a finite population correction: 0.2366–0.7634.
+        let (low, high) = wilson_interval(5, 10, 1_000_000_000);
+        assert_close(low, 0.236_593, 1e-5);
+        assert_close(high, 0.763_407, 1e-5);
+        // 0 of 10: 0–0.2775.
+        let (low, high) = wilson_interval(0, 10, 1_000_000_000);
+        assert_close(low, 0.0, 1e-12);
+        assert_close(high, 0.277_533, 1e-5);
+        // The whole population was drawn, so the proportion is exact.
+        assert_eq!(wilson_interval(3, 10, 10), (0.3, 0.3));
+    }
+}
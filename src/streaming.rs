@@ -0,0 +1,145 @@
+use std::sync::OnceLock;
+
+use anyhow::{Result, bail};
+use polars::prelude::*;
+
+use crate::report::{MissingValues, percentage};
+
+/// Bounds for the rows per streaming morsel picked from `--memory-limit`.
+const MIN_MORSEL_ROWS: usize = 1_000;
+const MAX_MORSEL_ROWS: usize = 100_000;
+
+/// Operators keep a few copies of a morsel alive at once (input, output,
+/// hash-table keys), so only a fraction of the budget goes to one batch.
+const MORSEL_HEADROOM: usize = 4;
+
+const ROWS_ALIAS: &str = "__mlcheck_rows";
+
+pub fn collect_streaming(lf: LazyFrame) -> PolarsResult<DataFrame> {
+    lf.collect_with_engine(Engine::Streaming)
+}
+
+/// Parses sizes like `512MB`, `2G` or `1.5GiB` into bytes, using powers of
+/// 1024 for every unit.
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{}'", input))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return Err(format!("unknown size unit '{}'", unit)),
+    };
+
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Morsel size handed to polars by [`set_morsel_rows`].
+static MORSEL_ROWS: OnceLock<usize> = OnceLock::new();
+
+/// Picks a morsel size so every thread's in-flight batch fits in `limit`
+/// bytes, estimated from the schema. Operators that keep state across
+/// morsels (deduplication, distinct counts, quantiles) need memory on top.
+pub fn morsel_rows(limit: u64, schema: &Schema) -> Result<usize> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let row_bytes: usize = schema
+        .iter_values()
+        .map(estimated_cell_bytes)
+        .sum::<usize>()
+        .max(1);
+
+    let rows = limit as usize / (threads * row_bytes * MORSEL_HEADROOM);
+    if rows < MIN_MORSEL_ROWS {
+        bail!(
+            "memory limit of {} bytes is too small for {} columns on {} threads",
+            limit,
+            schema.len(),
+            threads
+        );
+    }
+    Ok(rows.min(MAX_MORSEL_ROWS))
+}
+
+/// Sets the streaming engine's morsel size and returns the size in effect.
+/// Polars reads the setting once per process, so only the first call has an
+/// effect; later calls return the first size.
+///
+/// # Safety
+///
+/// Writes the process environment, so no other thread may read or write it
+/// during the call, and no streaming query may have run yet. Call it from a
+/// binary's `main`; a library embedding mlcheck should leave it alone.
+pub unsafe fn set_morsel_rows(rows: usize) -> usize {
+    *MORSEL_ROWS.get_or_init(|| {
+        // SAFETY: upheld by the caller.
+        unsafe { std::env::set_var("POLARS_IDEAL_MORSEL_SIZE", rows.to_string()) };
+        rows
+    })
+}
+
+/// The morsel size set by [`set_morsel_rows`] in this process, if any.
+pub fn morsel_rows_in_effect() -> Option<usize> {
+    MORSEL_ROWS.get().copied()
+}
+
+fn estimated_cell_bytes(dtype: &DataType) -> usize {
+    match dtype {
+        DataType::Boolean => 1,
+        DataType::String | DataType::Binary => 32,
+        dtype if dtype.is_primitive_numeric() || dtype.is_temporal() => 8,
+        _ => 64,
+    }
+}
+
+/// Missing values per column and the row count, from a single streaming scan.
+pub fn missing_values(lf: &LazyFrame, schema: &Schema) -> Result<(usize, Vec<MissingValues>)> {
+    let mut exprs: Vec<Expr> = schema
+        .iter_names()
+        .map(|name| col(name.clone()).null_count())
+        .collect();
+    exprs.push(len().alias(ROWS_ALIAS));
+
+    let counts = collect_streaming(lf.clone().select(exprs))?;
+    let rows = scalar_u64(&counts, ROWS_ALIAS)? as usize;
+
+    let missing = schema
+        .iter_names()
+        .map(|name| {
+            let count = scalar_u64(&counts, name)? as usize;
+            Ok(MissingValues {
+                column: name.to_string(),
+                count,
+                percentage: percentage(count, rows),
+                ci: None,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((rows, missing))
+}
+
+/// Number of rows that repeat an earlier row. Memory grows with the number of
+/// distinct rows, which is the one part of streaming validation that is not
+/// bounded by the morsel size.
+pub fn duplicate_rows(lf: &LazyFrame, rows: usize) -> Result<usize> {
+    let unique = collect_streaming(
+        lf.clone()
+            .unique(None, UniqueKeepStrategy::Any)
+            .select([len().alias(ROWS_ALIAS)]),
+    )?;
+    Ok(rows - scalar_u64(&unique, ROWS_ALIAS)? as usize)
+}
+
+fn scalar_u64(df: &DataFrame, name: &str) -> Result<u64> {
+    let col = df.column(name)?.cast(&DataType::UInt64)?;
+    Ok(col.u64()?.get(0).unwrap_or(0))
+}
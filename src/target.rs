@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::leakage::{LeakageSuspect, target_leakage};
+use crate::report::percentage;
+use crate::stats::{self, Quantile};
+use crate::streaming::collect_streaming;
+
+/// Numeric targets with at most this many distinct values are treated as
+/// class labels when the task is auto-detected.
+const MAX_AUTO_CLASSES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetTask {
+    Classification,
+    Regression,
+}
+
+#[derive(Debug, Clone)]
+pub struct TargetOptions {
+    /// `None` picks the task from the target's dtype and cardinality.
+    pub task: Option<TargetTask>,
+    pub min_class_count: usize,
+    pub test_size: f64,
+    /// Also look for features that leak the target.
+    pub leakage: bool,
+}
+
+impl Default for TargetOptions {
+    fn default() -> Self {
+        TargetOptions {
+            task: None,
+            min_class_count: 10,
+            test_size: 0.2,
+            leakage: true,
+        }
+    }
+}
+
+/// Checks that a stratified split's test fraction lies in `(0, 1)`; anything
+/// else leaves one side of the split empty.
+pub fn check_test_size(test_size: f64) -> Result<f64, String> {
+    if test_size > 0.0 && test_size < 1.0 {
+        Ok(test_size)
+    } else {
+        Err(format!("test size must be in (0, 1), got {}", test_size))
+    }
+}
+
+/// Parses `--test-size`.
+pub fn parse_test_size(input: &str) -> Result<f64, String> {
+    let test_size: f64 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid test size '{}'", input))?;
+    check_test_size(test_size)
+}
+
+#[derive(Debug, Serialize)]
+pub struct TargetReport {
+    pub column: String,
+    pub found: bool,
+    pub dtype: Option<String>,
+    pub unique: Option<usize>,
+    pub missing: Option<usize>,
+    pub missing_percentage: Option<f64>,
+    pub task: Option<TargetTask>,
+    pub classes: Option<ClassBalance>,
+    pub distribution: Option<TargetDistribution>,
+    /// Features that predict the target suspiciously well. Not computed in
+    /// streaming mode or when the leakage check is disabled.
+    pub leakage: Option<Vec<LeakageSuspect>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClassBalance {
+    /// Most frequent class first.
+    pub counts: Vec<ClassCount>,
+    /// Majority class count divided by minority class count.
+    pub imbalance_ratio: f64,
+    pub min_class_count: usize,
+    pub rare_classes: Vec<String>,
+    pub test_size: f64,
+    /// Classes expected to get no rows in a stratified split of `test_size`.
+    pub empty_in_split: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClassCount {
+    pub class: String,
+    pub count: usize,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TargetDistribution {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub std: f64,
+    pub skewness: f64,
+    pub quantiles: Vec<Quantile>,
+}
+
+pub fn analyze_target(
+    df: &DataFrame,
+    target_col: &str,
+    options: &TargetOptions,
+) -> Result<TargetReport> {
+    let Ok(series) = df.column(target_col) else {
+        return Ok(TargetReport {
+            column: target_col.to_string(),
+            found: false,
+            dtype: None,
+            unique: None,
+            missing: None,
+            missing_percentage: None,
+            task: None,
+            classes: None,
+            distribution: None,
+            leakage: None,
+        });
+    };
+
+    let null_count = series.null_count();
+    let unique = series.n_unique()?;
+    let task = options
+        .task
+        .unwrap_or_else(|| detect_task(series.dtype(), unique));
+
+    let (classes, distribution) = match task {
+        TargetTask::Classification => (Some(class_balance(series, options)?), None),
+        TargetTask::Regression => (None, regression_distribution(series)?),
+    };
+    let leakage = if options.leakage {
+        Some(target_leakage(df, series, task)?)
+    } else {
+        None
+    };
+
+    Ok(TargetReport {
+        column: target_col.to_string(),
+        found: true,
+        dtype: Some(format!("{:?}", series.dtype())),
+        unique: Some(unique),
+        missing: Some(null_count),
+        missing_percentage: Some(percentage(null_count, df.height())),
+        task: Some(task),
+        classes,
+        distribution,
+        leakage,
+    })
+}
+
+/// Streaming counterpart of [`analyze_target`]: a classification target is
+/// reduced to per-class counts. For a regression target the quantiles buffer
+/// the column's values, so memory still grows with the row count.
+pub fn analyze_target_lazy(
+    lf: &LazyFrame,
+    schema: &Schema,
+    target_col: &str,
+    rows: usize,
+    options: &TargetOptions,
+) -> Result<TargetReport> {
+    let Some(dtype) = schema.get(target_col) else {
+        return Ok(TargetReport {
+            column: target_col.to_string(),
+            found: false,
+            dtype: None,
+            unique: None,
+            missing: None,
+            missing_percentage: None,
+            task: None,
+            classes: None,
+            distribution: None,
+            leakage: None,
+        });
+    };
+
+    let summary = collect_streaming(lf.clone().select([
+        col(target_col).n_unique().alias("unique"),
+        col(target_col).null_count().alias("missing"),
+    ]))?;
+    let unique = scalar_usize(&summary, "unique")?;
+    let null_count = scalar_usize(&summary, "missing")?;
+    let task = options.task.unwrap_or_else(|| detect_task(dtype, unique));
+
+    let (classes, distribution) = match task {
+        TargetTask::Classification => {
+            let grouped = collect_streaming(
+                lf.clone()
+                    .select([col(target_col).cast(DataType::String)])
+                    .drop_nulls(None)
+                    .group_by([col(target_col)])
+                    .agg([len().alias("count")]),
+            )?;
+            let labels = grouped.column(target_col)?.str()?.clone();
+            let counts = grouped.column("count")?.cast(&DataType::UInt64)?;
+            let counts = labels
+                .into_iter()
+                .zip(counts.u64()?)
+                .filter_map(|(label, count)| Some((label?.to_string(), count? as usize)))
+                .collect();
+            (Some(class_balance_from_counts(counts, options)), None)
+        }
+        TargetTask::Regression if dtype.is_primitive_numeric() => {
+            (None, regression_distribution_lazy(lf, target_col)?)
+        }
+        TargetTask::Regression => (None, None),
+    };
+
+    Ok(TargetReport {
+        column: target_col.to_string(),
+        found: true,
+        dtype: Some(format!("{:?}", dtype)),
+        unique: Some(unique),
+        missing: Some(null_count),
+        missing_percentage: Some(percentage(null_count, rows)),
+        task: Some(task),
+        classes,
+        distribution,
+        leakage: None,
+    })
+}
+
+/// Computes the regression summary with aggregations. Polars buffers the
+/// column for the quantiles; the other statistics stream. Skewness needs the
+/// mean first, hence the second pass.
+fn regression_distribution_lazy(
+    lf: &LazyFrame,
+    target_col: &str,
+) -> Result<Option<TargetDistribution>> {
+    let x = || col(target_col).cast(DataType::Float64);
+
+    let mut aggs = vec![
+        x().min().alias("min"),
+        x().max().alias("max"),
+        x().mean().alias("mean"),
+        x().std(1).alias("std"),
+    ];
+    for (i, &q) in stats::SUMMARY_QUANTILES.iter().enumerate() {
+        aggs.push(
+            x().quantile(lit(q), QuantileMethod::Linear)
+                .alias(format!("q{}", i)),
+        );
+    }
+    let summary = collect_streaming(lf.clone().select(aggs))?;
+
+    let (Some(min), Some(max), Some(mean)) = (
+        scalar_f64(&summary, "min")?,
+        scalar_f64(&summary, "max")?,
+        scalar_f64(&summary, "mean")?,
+    ) else {
+        return Ok(None);
+    };
+
+    let moments = collect_streaming(lf.clone().select([
+        (x() - lit(mean)).pow(2).mean().alias("m2"),
+        (x() - lit(mean)).pow(3).mean().alias("m3"),
+    ]))?;
+    let m2 = scalar_f64(&moments, "m2")?.unwrap_or(0.0);
+    let m3 = scalar_f64(&moments, "m3")?.unwrap_or(0.0);
+    let skewness = if m2 == 0.0 { 0.0 } else { m3 / m2.powf(1.5) };
+
+    let mut quantiles = Vec::new();
+    for (i, &q) in stats::SUMMARY_QUANTILES.iter().enumerate() {
+        if let Some(value) = scalar_f64(&summary, &format!("q{}", i))? {
+            quantiles.push(Quantile { q, value });
+        }
+    }
+
+    Ok(Some(TargetDistribution {
+        min,
+        max,
+        mean,
+        std: scalar_f64(&summary, "std")?.unwrap_or(0.0),
+        skewness,
+        quantiles,
+    }))
+}
+
+fn scalar_f64(df: &DataFrame, name: &str) -> Result<Option<f64>> {
+    let col = df.column(name)?.cast(&DataType::Float64)?;
+    Ok(col.f64()?.get(0))
+}
+
+fn scalar_usize(df: &DataFrame, name: &str) -> Result<usize> {
+    let col = df.column(name)?.cast(&DataType::UInt64)?;
+    Ok(col.u64()?.get(0).unwrap_or(0) as usize)
+}
+
+fn detect_task(dtype: &DataType, unique: usize) -> TargetTask {
+    if dtype.is_primitive_numeric() && unique > MAX_AUTO_CLASSES {
+        TargetTask::Regression
+    } else {
+        TargetTask::Classification
+    }
+}
+
+fn class_balance(series: &Column, options: &TargetOptions) -> Result<ClassBalance> {
+    let labels = series.cast(&DataType::String)?;
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for label in labels.str()?.into_iter().flatten() {
+        *counts.entry(label.to_string()).or_default() += 1;
+    }
+    Ok(class_balance_from_counts(counts, options))
+}
+
+fn class_balance_from_counts(
+    counts: HashMap<String, usize>,
+    options: &TargetOptions,
+) -> ClassBalance {
+    let total: usize = counts.values().sum();
+    let mut counts: Vec<ClassCount> = counts
+        .into_iter()
+        .map(|(class, count)| ClassCount {
+            class,
+            count,
+            percentage: percentage(count, total),
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.class.cmp(&b.class)));
+
+    let imbalance_ratio = match (counts.first(), counts.last()) {
+        (Some(majority), Some(minority)) => majority.count as f64 / minority.count as f64,
+        _ => 1.0,
+    };
+
+    let rare_classes = counts
+        .iter()
+        .filter(|c| c.count < options.min_class_count)
+        .map(|c| c.class.clone())
+        .collect();
+
+    // A stratified split keeps each class's share, so a class ends up with
+    // roughly `count * test_size` test rows. Fewer than one row on either
+    // side means the split cannot represent it.
+    let empty_in_split = counts
+        .iter()
+        .filter(|c| {
+            let test_rows = c.count as f64 * options.test_size;
+            let train_rows = c.count as f64 - test_rows;
+            test_rows < 1.0 || train_rows < 1.0
+        })
+        .map(|c| c.class.clone())
+        .collect();
+
+    ClassBalance {
+        counts,
+        imbalance_ratio,
+        min_class_count: options.min_class_count,
+        rare_classes,
+        test_size: options.test_size,
+        empty_in_split,
+    }
+}
+
+fn regression_distribution(series: &Column) -> Result<Option<TargetDistribution>> {
+    if !series.dtype().is_primitive_numeric() {
+        return Ok(None);
+    }
+
+    let cast = series.cast(&DataType::Float64)?;
+    let mut values: Vec<f64> = cast.f64()?.into_iter().flatten().collect();
+    values.sort_by(f64::total_cmp);
+
+    let (Some(&min), Some(&max), Some(mean)) =
+        (values.first(), values.last(), stats::mean(&values))
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(TargetDistribution {
+        min,
+        max,
+        mean,
+        std: stats::std_dev(&values).unwrap_or(0.0),
+        skewness: stats::skewness(&values).unwrap_or(0.0),
+        quantiles: stats::summary_quantiles(&values),
+    }))
+}
@@ -0,0 +1,507 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use polars::prelude::*;
+
+use crate::check::{
+    CheckKind, ConstantColumnsCheck, ContractCheck, DuplicatesCheck, IdentifierCheck,
+    MissingValuesCheck, OutlierCheck, TargetCheck, load_warning_findings,
+};
+use crate::contract::ContractReport;
+use crate::dialect::CsvOptions;
+use crate::html::MissingMap;
+use crate::identifiers::Identifiers;
+use crate::loader::{
+    Dataset, Format, Scan, input_size, read_dataset, read_dataset_sample, scan_dataset,
+};
+use crate::outliers::Outliers;
+use crate::profile::{self, ColumnProfile};
+use crate::report::{
+    ConstantColumns, Duplicates, Execution, Finding, MissingValues, Overview,
+    REPORT_SCHEMA_VERSION, Severity, Spilled, ValidationReport, percentage,
+};
+use crate::sample::{self, Sample, SampleInfo, SampleSize};
+use crate::streaming;
+use crate::target::{TargetOptions, TargetReport, analyze_target_lazy};
+
+/// Most frequent values charted per text column in HTML validation reports.
+const VISUALS_TOP_K: usize = 10;
+
+/// Everything `validate` runs, with the same defaults as the CLI.
+pub struct ValidateOptions {
+    pub target: Option<String>,
+    pub target_options: TargetOptions,
+    pub max_imbalance_ratio: Option<f64>,
+    pub missing: MissingValuesCheck,
+    pub duplicates: DuplicatesCheck,
+    pub outliers: OutlierCheck,
+    pub constant: ConstantColumnsCheck,
+    /// The target column is always left out, whatever `identifiers.target`
+    /// says.
+    pub identifiers: IdentifierCheck,
+    /// Path of a YAML schema contract.
+    pub schema: Option<String>,
+    /// Checks to run; the target and contract checks also need `target` and
+    /// `schema`.
+    pub checks: Vec<CheckKind>,
+    /// Columns dropped before any check runs.
+    pub ignore_columns: Vec<String>,
+    /// Overrides format detection, e.g. for data piped through stdin.
+    pub input_format: Option<Format>,
+    pub csv: CsvOptions,
+    /// The report fails when a finding of this severity or worse is raised.
+    pub fail_on: Severity,
+    /// Run on a random sample instead of every row.
+    pub sample: Option<SampleSize>,
+    pub seed: u64,
+    /// Scan the file with the streaming engine. Outlier and target-leakage
+    /// detection are skipped, each with an info finding, and a schema
+    /// contract is rejected.
+    pub streaming: bool,
+    /// Reported with the execution details. Enforcing it is up to the
+    /// caller: the CLI installs [`crate::budget::Budget`] as its allocator
+    /// and sizes morsels with [`streaming::morsel_rows`].
+    pub memory_limit: Option<u64>,
+    /// Where streaming mode decompresses compressed input; the system temp
+    /// directory (`TMPDIR`) when unset.
+    pub spill_dir: Option<PathBuf>,
+    /// Collect the column profiles and missing-value map drawn in HTML
+    /// reports.
+    pub visuals: bool,
+}
+
+impl Default for ValidateOptions {
+    fn default() -> Self {
+        ValidateOptions {
+            target: None,
+            target_options: TargetOptions::default(),
+            max_imbalance_ratio: None,
+            missing: MissingValuesCheck::default(),
+            duplicates: DuplicatesCheck::default(),
+            outliers: OutlierCheck::default(),
+            constant: ConstantColumnsCheck::default(),
+            identifiers: IdentifierCheck::default(),
+            schema: None,
+            checks: CheckKind::ALL.to_vec(),
+            ignore_columns: Vec::new(),
+            input_format: None,
+            csv: CsvOptions::default(),
+            fail_on: Severity::Error,
+            sample: None,
+            seed: 42,
+            streaming: false,
+            memory_limit: None,
+            spill_dir: None,
+            visuals: false,
+        }
+    }
+}
+
+/// Everything the checks measured, before it is turned into findings.
+struct CheckResults {
+    warnings: Vec<String>,
+    overview: Overview,
+    execution: Execution,
+    sample: Option<SampleInfo>,
+    /// Enabled checks that could not run, in streaming mode.
+    skipped: Vec<CheckKind>,
+    columns: Vec<String>,
+    missing: Option<Vec<MissingValues>>,
+    duplicates: Option<Duplicates>,
+    outliers: Option<Outliers>,
+    constant: Option<ConstantColumns>,
+    identifiers: Option<Identifiers>,
+    target: Option<TargetReport>,
+    contract: Option<ContractReport>,
+    profiles: Vec<ColumnProfile>,
+    missing_map: Option<MissingMap>,
+}
+
+/// Loads `path`, or stdin for `-`, and runs every configured check on it.
+pub fn validate_path(path: &str, options: &ValidateOptions) -> Result<ValidationReport> {
+    let checks = if options.streaming {
+        check_streaming(options)?;
+        let scan = scan_dataset(
+            path,
+            options.input_format,
+            &options.csv,
+            Some(&options.spill_dir()),
+        )?;
+        run_checks_streaming(path, scan, options)?
+    } else {
+        // Load the contract first so a typo in it fails before a long read.
+        let contract = contract_check(options)?;
+        let (Dataset { df, warnings, .. }, sample) = match options.sample {
+            Some(size) => {
+                let (dataset, sample) = read_dataset_sample(
+                    path,
+                    options.input_format,
+                    &options.csv,
+                    size,
+                    options.seed,
+                )?;
+                (dataset, Some(sample))
+            }
+            None => (
+                read_dataset(path, options.input_format, &options.csv)?,
+                None,
+            ),
+        };
+        run_checks(&df, warnings, sample, contract.as_ref(), options)?
+    };
+    Ok(build_report(path, checks, options))
+}
+
+/// Streaming checks on a dataset already scanned with [`scan_dataset`], so
+/// the caller can look at its schema first, e.g. to size morsels with
+/// [`streaming::morsel_rows`]. `path` names the data in the report.
+pub fn validate_scan(
+    path: &str,
+    scan: Scan,
+    options: &ValidateOptions,
+) -> Result<ValidationReport> {
+    check_streaming(options)?;
+    let checks = run_checks_streaming(path, scan, options)?;
+    Ok(build_report(path, checks, options))
+}
+
+fn check_streaming(options: &ValidateOptions) -> Result<()> {
+    if options.schema.is_some() && options.enabled(CheckKind::Contract) {
+        bail!("the schema contract cannot be checked in streaming mode");
+    }
+    Ok(())
+}
+
+/// Runs every configured check on a frame that is already in memory. `name`
+/// identifies the data in the report. `streaming` and `memory_limit` are
+/// ignored.
+pub fn validate_frame(
+    df: &DataFrame,
+    name: &str,
+    options: &ValidateOptions,
+) -> Result<ValidationReport> {
+    let contract = contract_check(options)?;
+    let checks = match options.sample {
+        Some(size) => {
+            let (df, sample) = sample::sample(df, size, options.seed)?;
+            run_checks(&df, Vec::new(), Some(sample), contract.as_ref(), options)?
+        }
+        None => run_checks(df, Vec::new(), None, contract.as_ref(), options)?,
+    };
+    Ok(build_report(name, checks, options))
+}
+
+impl ValidateOptions {
+    fn enabled(&self, check: CheckKind) -> bool {
+        self.checks.contains(&check)
+    }
+
+    /// Directory for decompressed copies in streaming mode.
+    pub fn spill_dir(&self) -> PathBuf {
+        self.spill_dir.clone().unwrap_or_else(std::env::temp_dir)
+    }
+}
+
+fn contract_check(options: &ValidateOptions) -> Result<Option<ContractCheck>> {
+    if !options.enabled(CheckKind::Contract) {
+        return Ok(None);
+    }
+    options
+        .schema
+        .as_deref()
+        .map(ContractCheck::from_path)
+        .transpose()
+}
+
+fn target_check(options: &ValidateOptions, column: &str) -> TargetCheck {
+    TargetCheck {
+        column: column.to_string(),
+        options: TargetOptions {
+            leakage: options.target_options.leakage && options.enabled(CheckKind::Leakage),
+            ..options.target_options.clone()
+        },
+        max_imbalance_ratio: options.max_imbalance_ratio,
+    }
+}
+
+fn identifier_check(options: &ValidateOptions) -> IdentifierCheck {
+    IdentifierCheck {
+        target: options.target.clone(),
+        ..options.identifiers.clone()
+    }
+}
+
+/// The `--target` column, unless the target check is disabled.
+fn target_column(options: &ValidateOptions) -> Option<&str> {
+    options
+        .target
+        .as_deref()
+        .filter(|_| options.enabled(CheckKind::Target))
+}
+
+fn build_report(file: &str, checks: CheckResults, options: &ValidateOptions) -> ValidationReport {
+    let mut findings = load_warning_findings(&checks.warnings);
+    findings.extend(checks.skipped.iter().map(|check| Finding {
+        check: check.to_string(),
+        column: None,
+        severity: Severity::Info,
+        message: "skipped in streaming mode, which cannot hold whole columns in memory".to_string(),
+    }));
+    if let Some(missing) = &checks.missing {
+        findings.extend(options.missing.findings(missing));
+    }
+    if let Some(duplicates) = &checks.duplicates {
+        findings.extend(options.duplicates.findings(duplicates));
+    }
+    if let Some(outliers) = &checks.outliers {
+        findings.extend(options.outliers.findings(outliers));
+    }
+    if let Some(constant) = &checks.constant {
+        findings.extend(options.constant.findings(constant));
+    }
+    if let Some(identifiers) = &checks.identifiers {
+        findings.extend(identifier_check(options).findings(identifiers));
+    }
+    if let Some(contract) = &checks.contract {
+        findings.extend(ContractCheck::findings(contract));
+    }
+    if let Some(target) = &checks.target {
+        findings.extend(target_check(options, &target.column).findings(target));
+    }
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    let passed = findings.iter().all(|f| f.severity < options.fail_on);
+
+    ValidationReport {
+        schema_version: REPORT_SCHEMA_VERSION,
+        file: file.to_string(),
+        warnings: checks.warnings,
+        overview: checks.overview,
+        execution: checks.execution,
+        sample: checks.sample,
+        disabled: CheckKind::ALL
+            .into_iter()
+            .filter(|&check| !options.enabled(check))
+            .collect(),
+        skipped: checks.skipped,
+        columns: checks.columns,
+        missing: checks.missing,
+        duplicates: checks.duplicates,
+        outliers: checks.outliers,
+        constant: checks.constant,
+        identifiers: checks.identifiers,
+        target: checks.target,
+        contract: checks.contract,
+        findings,
+        fail_on: options.fail_on,
+        passed,
+        profiles: checks.profiles,
+        missing_map: checks.missing_map,
+    }
+}
+
+/// `df` is already sampled when `sample` is given.
+fn run_checks(
+    df: &DataFrame,
+    warnings: Vec<String>,
+    sample: Option<Sample>,
+    contract: Option<&ContractCheck>,
+    options: &ValidateOptions,
+) -> Result<CheckResults> {
+    let df = df.drop_many(&options.ignore_columns);
+
+    let mut missing = options
+        .enabled(CheckKind::Missing)
+        .then(|| options.missing.measure(&df));
+    let mut duplicates = if options.enabled(CheckKind::Duplicates) {
+        Some(options.duplicates.measure(&df)?)
+    } else {
+        None
+    };
+    let mut outliers = if options.enabled(CheckKind::Outliers) {
+        Some(options.outliers.measure(&df)?)
+    } else {
+        None
+    };
+    let constant = if options.enabled(CheckKind::Constant) {
+        Some(options.constant.measure(&df)?)
+    } else {
+        None
+    };
+    let identifiers = if options.enabled(CheckKind::Identifiers) {
+        Some(identifier_check(options).measure(&df)?)
+    } else {
+        None
+    };
+    if let Some(sample) = &sample {
+        for m in missing.iter_mut().flatten() {
+            m.ci = Some(sample.info.interval(m.count));
+        }
+        if let Some(duplicates) = &mut duplicates {
+            duplicates.sampled = true;
+        }
+        // Point examples at rows of the file, not positions in the sample.
+        for col in outliers.iter_mut().flat_map(|o| &mut o.columns) {
+            for idx in &mut col.examples {
+                *idx = sample.source_rows[*idx];
+            }
+        }
+    }
+
+    let target = match target_column(options) {
+        Some(column) => Some(target_check(options, column).measure(&df)?),
+        None => None,
+    };
+
+    let contract = match contract {
+        Some(contract) => Some(contract.measure(&df)?),
+        None => None,
+    };
+
+    // Charts for the HTML report
+    let (profiles, missing_map) = if options.visuals {
+        let profiles = df
+            .get_columns()
+            .iter()
+            .map(|col| profile::profile_column(col, VISUALS_TOP_K))
+            .collect::<Result<_>>()?;
+        (profiles, Some(MissingMap::from_frame(&df)?))
+    } else {
+        (Vec::new(), None)
+    };
+
+    Ok(CheckResults {
+        warnings,
+        overview: Overview {
+            rows: df.height(),
+            columns: df.width(),
+            size_bytes: df.estimated_size(),
+        },
+        execution: Execution {
+            streaming: false,
+            memory_limit_bytes: None,
+            morsel_rows: None,
+            spilled: None,
+        },
+        sample: sample.map(|s| s.info),
+        skipped: Vec::new(),
+        columns: df
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect(),
+        missing,
+        duplicates,
+        outliers,
+        constant,
+        identifiers,
+        target,
+        contract,
+        profiles,
+        missing_map,
+    })
+}
+
+/// Bounded-memory variant of [`run_checks`]: missing values, duplicates,
+/// constant and identifier columns and target statistics are computed by the
+/// polars streaming engine. Outlier and target-leakage detection need every
+/// value of a column at once; enabled ones are listed as skipped.
+fn run_checks_streaming(path: &str, scan: Scan, options: &ValidateOptions) -> Result<CheckResults> {
+    // `spills` keeps decompressed copies alive until the last query.
+    let Scan {
+        mut lf,
+        warnings,
+        spills,
+        ..
+    } = scan;
+    let spilled = if spills.is_empty() {
+        None
+    } else {
+        let bytes = spills
+            .iter()
+            .map(|spill| Ok(std::fs::metadata(spill.path())?.len()))
+            .sum::<Result<u64>>()?;
+        Some(Spilled {
+            dir: options.spill_dir().display().to_string(),
+            bytes,
+        })
+    };
+    let mut skipped = Vec::new();
+    if options.enabled(CheckKind::Outliers) {
+        skipped.push(CheckKind::Outliers);
+    }
+    if let Some(target_col) = target_column(options)
+        && target_check(options, target_col).options.leakage
+    {
+        skipped.push(CheckKind::Leakage);
+    }
+    let mut schema = lf.collect_schema()?;
+    if options.ignore_columns.iter().any(|c| schema.contains(c)) {
+        let kept: Vec<Expr> = schema
+            .iter_names()
+            .filter(|name| !options.ignore_columns.iter().any(|c| c == name.as_str()))
+            .map(|name| col(name.clone()))
+            .collect();
+        lf = lf.select(kept);
+        schema = lf.collect_schema()?;
+    }
+
+    let (rows, missing) = streaming::missing_values(&lf, &schema)?;
+    let duplicates = if options.enabled(CheckKind::Duplicates) {
+        Some(streaming::duplicate_rows(&lf, rows)?)
+    } else {
+        None
+    };
+    let constant = if options.enabled(CheckKind::Constant) {
+        Some(options.constant.measure_lazy(&lf, &schema, rows)?)
+    } else {
+        None
+    };
+    let identifiers = if options.enabled(CheckKind::Identifiers) {
+        Some(identifier_check(options).measure_lazy(&lf, &schema)?)
+    } else {
+        None
+    };
+
+    let target = match target_column(options) {
+        Some(target_col) => Some(analyze_target_lazy(
+            &lf,
+            &schema,
+            target_col,
+            rows,
+            &options.target_options,
+        )?),
+        None => None,
+    };
+
+    Ok(CheckResults {
+        warnings,
+        overview: Overview {
+            rows,
+            columns: schema.len(),
+            size_bytes: input_size(path)? as usize,
+        },
+        execution: Execution {
+            streaming: true,
+            memory_limit_bytes: options.memory_limit,
+            morsel_rows: streaming::morsel_rows_in_effect(),
+            spilled,
+        },
+        sample: None,
+        skipped,
+        columns: schema.iter_names().map(|name| name.to_string()).collect(),
+        missing: options.enabled(CheckKind::Missing).then_some(missing),
+        duplicates: duplicates.map(|count| Duplicates {
+            count,
+            percentage: percentage(count, rows),
+            sampled: false,
+        }),
+        outliers: None,
+        constant,
+        identifiers,
+        target,
+        contract: None,
+        profiles: Vec::new(),
+        missing_map: None,
+    })
+}